@@ -0,0 +1,137 @@
+//! Generates the module list that the `in-process` feature compiles into the `advent_of_code` binary,
+//! and the example tests of every day that has a manifest in `data/examples/DD.toml`.
+use std::{env, fs, path::Path};
+
+// only the parser is used here; its tests are compiled in with the `test_lib` feature.
+#[allow(dead_code, unused_imports)]
+#[path = "src/template/example_manifest.rs"]
+mod example_manifest;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let stem = path.file_stem()?.to_str()?.to_string();
+                    // days are named `DD`, or `YYYY-DD` in the multi-year layout.
+                    let day = match stem.split_once('-') {
+                        Some((year, day)) if is_number(year, 4) => day,
+                        Some(_) => return None,
+                        None => &stem,
+                    };
+                    let is_day = path.extension()? == "rs" && is_number(day, 2);
+                    is_day.then(|| (stem.replace('-', "_"), path.to_string_lossy().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let mut code = String::from("// @generated by build.rs\n");
+
+    for (day, path) in &days {
+        code.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {path:?}]\nmod day_{day};\n"
+        ));
+    }
+
+    code.push_str("\npub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[\n");
+    for (day, _) in &days {
+        code.push_str(&format!("    day_{day}::SOLUTION,\n"));
+    }
+    code.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), code).unwrap();
+
+    let examples_dir = Path::new(&out_dir).join("examples");
+    fs::create_dir_all(&examples_dir).unwrap();
+
+    for (_, path) in &days {
+        let stem = Path::new(path).file_stem().unwrap().to_string_lossy();
+        let code = example_tests(Path::new(&manifest_dir), &stem);
+        fs::write(examples_dir.join(format!("{stem}.rs")), code).unwrap();
+    }
+}
+
+/// Tests for the examples in the manifest of the day `stem`, e.g. `data/2023/examples/05.toml` for `2023-05`.
+fn example_tests(root: &Path, stem: &str) -> String {
+    let (data_dir, day) = match stem.split_once('-') {
+        Some((year, day)) => (root.join("data").join(year), day),
+        None => (root.join("data"), stem),
+    };
+
+    let examples_dir = data_dir.join("examples");
+    if examples_dir.is_dir() {
+        println!("cargo:rerun-if-changed={}", examples_dir.display());
+    }
+
+    let mut code = String::from("// @generated by build.rs\n");
+
+    let Ok(manifest) = fs::read_to_string(examples_dir.join(format!("{day}.toml"))) else {
+        return code;
+    };
+
+    let examples = match example_manifest::parse(&manifest) {
+        Ok(examples) => examples,
+        Err(e) => {
+            let message = format!("invalid example manifest of {stem}: {e}");
+            println!("cargo:warning={message}");
+            code.push_str(&format!(
+                "\n#[test]\nfn manifest() {{\n    panic!(\"{{}}\", {message:?});\n}}\n"
+            ));
+            return code;
+        }
+    };
+
+    for example in &examples {
+        let name: String = example
+            .file
+            .trim_end_matches(".txt")
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        code.push_str(&format!(
+            "\n#[test]\nfn example_{name}() {{\n    \
+                let example = advent_of_code::template::examples::Example {{\n        \
+                    file: {:?}.into(),\n        \
+                    part_1: {},\n        \
+                    part_2: {},\n        \
+                    params: vec![{}],\n    \
+                }};\n    \
+                advent_of_code::template::examples::check(&super::SOLUTION, &example);\n\
+            }}\n",
+            example.file,
+            optional_string(example.part_1.as_deref()),
+            optional_string(example.part_2.as_deref()),
+            example
+                .params
+                .iter()
+                .map(|(key, value)| format!("({key:?}.into(), {value:?}.into())"))
+                .collect::<Vec<_>>()
+                .join(", "),
+        ));
+    }
+
+    code
+}
+
+fn optional_string(s: Option<&str>) -> String {
+    match s {
+        Some(s) => format!("Some({s:?}.into())"),
+        None => "None".into(),
+    }
+}
+
+fn is_number(s: &str, len: usize) -> bool {
+    s.len() == len && s.chars().all(|c| c.is_ascii_digit())
+}
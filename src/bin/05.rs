@@ -1,8 +1,11 @@
-use std::collections::{HashSet};
+use std::collections::HashSet;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse = parse_input);
 
-fn parse_input(input: &str) -> (HashSet<(u32, u32)>, Vec<Vec<u32>>) {
+/// Ordering rules and updates.
+type Input = (HashSet<(u32, u32)>, Vec<Vec<u32>>);
+
+fn parse_input(input: &str) -> Input {
     let mut rules: HashSet<(u32, u32)> = HashSet::new();
     let mut updates: Vec<Vec<u32>> = Vec::new();
 
@@ -32,21 +35,22 @@ fn parse_input(input: &str) -> (HashSet<(u32, u32)>, Vec<Vec<u32>>) {
 fn get_sorted_update(rules: &HashSet<(u32, u32)>, update: &Vec<u32>) -> Vec<u32> {
     let mut result = update.clone();
 
-    result.sort_by(|a, b| if rules.contains(&(*a, *b)) {
-        std::cmp::Ordering::Less
-    } else {
-        std::cmp::Ordering::Greater
+    result.sort_by(|a, b| {
+        if rules.contains(&(*a, *b)) {
+            std::cmp::Ordering::Less
+        } else {
+            std::cmp::Ordering::Greater
+        }
     });
-    
+
     result
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one((rules, updates): &Input) -> Option<u32> {
     let mut total: u32 = 0;
-    let (rules, updates) = parse_input(input);
 
     for update in updates {
-        let sorted = get_sorted_update(&rules, &update);
+        let sorted = get_sorted_update(rules, update);
         if update.iter().zip(&sorted).all(|(a, b)| a == b) {
             total += update[update.len() / 2];
         }
@@ -55,12 +59,11 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(total)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two((rules, updates): &Input) -> Option<u32> {
     let mut total: u32 = 0;
-    let (rules, updates) = parse_input(input);
 
     for update in updates {
-        let sorted = get_sorted_update(&rules, &update);
+        let sorted = get_sorted_update(rules, update);
         if !update.iter().zip(&sorted).all(|(a, b)| a == b) {
             total += sorted[sorted.len() / 2];
         }
@@ -75,13 +78,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(123));
     }
 }
@@ -1,40 +1,62 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+#[cfg(feature = "today")]
+use advent_of_code::template::commands::today;
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, time, CommandError,
+};
 use args::{parse, AppArguments};
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+/// Every scaffolded solution, compiled into this binary so `all` and `time` can run them in-process.
+#[cfg(all(feature = "in-process", not(feature = "dhat-heap"), not(test)))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::time::TimeOptions;
+    use advent_of_code::template::export::Export;
+    use advent_of_code::template::input::InputSource;
+    use advent_of_code::template::Puzzle;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            day: Puzzle,
         },
         Read {
-            day: Day,
+            day: Puzzle,
+        },
+        Examples {
+            day: Puzzle,
+            blocks: Vec<usize>,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            day: Puzzle,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            day: Puzzle,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            check: bool,
+            input: InputSource,
+            inputs_dir: Option<String>,
+            watch: bool,
         },
         All {
+            year: Option<u16>,
             release: bool,
+            jobs: usize,
+            check: bool,
+            timeout: Option<Duration>,
         },
         Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
+            day: Option<Puzzle>,
+            year: Option<u16>,
+            history: Option<Puzzle>,
+            options: TimeOptions,
         },
         #[cfg(feature = "today")]
         Today,
@@ -45,16 +67,41 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: args.opt_value_from_str("--year")?,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                check: args.contains("--check"),
+                timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
             },
             Some("time") => {
-                let all = args.contains("--all");
+                let run_all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_str("--threshold")?;
+                let history = args.opt_value_from_str("--history")?;
+                let year = args.opt_value_from_str("--year")?;
+                let timeout = args.opt_value_from_fn("--timeout", parse_timeout)?;
+                let export = match (
+                    args.opt_value_from_str("--export")?,
+                    args.opt_value_from_str("--output")?,
+                ) {
+                    (Some(format), Some(path)) => Some(Export { format, path }),
+                    (None, None) => None,
+                    _ => return Err("--export and --output must be passed together".into()),
+                };
 
                 AppArguments::Time {
-                    all,
                     day: args.opt_free_from_str()?,
-                    store,
+                    year,
+                    history,
+                    options: TimeOptions {
+                        run_all,
+                        store,
+                        compare,
+                        threshold,
+                        timeout,
+                        export,
+                    },
                 }
             }
             Some("download") => AppArguments::Download {
@@ -63,27 +110,60 @@ mod args {
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("examples") => AppArguments::Examples {
+                blocks: args.values_from_str("--block")?,
+                overwrite: args.contains("--overwrite"),
+                day: args.free_from_str()?,
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let check = args.contains("--check");
+                let path: Option<String> = args.opt_value_from_str("--input")?;
+                let example = args.contains("--example");
+                let inputs_dir = args.opt_value_from_str("--inputs-dir")?;
+                let watch = args.contains("--watch");
+                let day = args.free_from_str()?;
+
+                // `--example` takes an optional example number, which is the free argument after the day.
+                let input = match path.as_deref() {
+                    Some("-") => InputSource::Stdin,
+                    Some(path) => InputSource::Path(path.into()),
+                    None if example => InputSource::Example(args.opt_free_from_str()?),
+                    None => InputSource::Puzzle,
+                };
+
+                if watch && (submit.is_some() || check || inputs_dir.is_some()) {
+                    return Err(
+                        "--watch cannot be combined with --submit, --check or --inputs-dir".into(),
+                    );
+                }
+
+                if watch && input == InputSource::Stdin {
+                    return Err("--watch cannot read its input from stdin".into());
+                }
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    submit,
+                    check,
+                    input,
+                    inputs_dir,
+                    watch,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some(x) => return Err(format!("unknown command: {x}").into()),
+            None => return Err("no command specified.".into()),
         };
 
         let remaining = args.finish();
@@ -93,52 +173,90 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses a timeout given in seconds, e.g. `30` or `0.5`.
+    fn parse_timeout(s: &str) -> Result<Duration, String> {
+        s.parse::<f64>()
+            .ok()
+            .filter(|secs| secs.is_finite() && *secs > 0.0)
+            .map(Duration::from_secs_f64)
+            .ok_or_else(|| format!("expecting a timeout in seconds, got \"{s}\""))
+    }
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
-        }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+    #[cfg(all(feature = "in-process", not(feature = "dhat-heap"), not(test)))]
+    advent_of_code::template::registry::register(solutions::SOLUTIONS);
+
+    let result = parse()
+        .map_err(|err| CommandError::Usage(err.to_string()))
+        .and_then(run);
+
+    if let Err(err) = result {
+        err.exit();
+    }
+}
+
+fn run(args: AppArguments) -> Result<(), CommandError> {
+    match args {
+        AppArguments::All {
+            year,
+            release,
+            jobs,
+            check,
+            timeout,
+        } => all::handle(year, release, jobs, check, timeout),
+        AppArguments::Time {
+            day,
+            year,
+            history,
+            options,
+        } => time::handle(day, year, history, options),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Read { day } => read::handle(day),
+        AppArguments::Examples {
+            day,
+            blocks,
+            overwrite,
+        } => examples::handle(day, &blocks, overwrite),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+        } => {
+            scaffold::handle(day, overwrite)?;
+            if download {
+                download::handle(day)?;
             }
-        },
-    };
+            Ok(())
+        }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            input,
+            watch: true,
+            ..
+        } => solve::watch(day, release, dhat, &input),
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            check,
+            input,
+            inputs_dir,
+            watch: false,
+        } => solve::handle(
+            day,
+            release,
+            dhat,
+            submit,
+            check,
+            &input,
+            inputs_dir.as_deref(),
+        ),
+        #[cfg(feature = "today")]
+        AppArguments::Today => today::handle(),
+    }
 }
@@ -0,0 +1,269 @@
+/// A global allocator that counts heap usage, so memory figures can be reported next to timings.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tinyjson::JsonValue;
+
+/// Whether the counting allocator is installed. It gives way to dhat's allocator under `dhat-heap`.
+pub const IS_ENABLED: bool = cfg!(all(feature = "count-allocs", not(feature = "dhat-heap")));
+
+#[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+static BASELINE: AtomicU64 = AtomicU64::new(0);
+static TOTAL: AtomicU64 = AtomicU64::new(0);
+static COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps the system allocator and keeps track of the bytes and allocations it hands out.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    let size = size as u64;
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+    TOTAL.fetch_add(size, Ordering::Relaxed);
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    CURRENT.fetch_sub(size as u64, Ordering::Relaxed);
+}
+
+/// Start a new measurement. Memory that is already allocated does not count towards the peak.
+pub fn reset() {
+    let current = CURRENT.load(Ordering::Relaxed);
+    BASELINE.store(current, Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    TOTAL.store(0, Ordering::Relaxed);
+    COUNT.store(0, Ordering::Relaxed);
+}
+
+/// Memory usage since the last [`reset`], if the counting allocator is installed.
+pub fn snapshot() -> Option<MemoryStats> {
+    IS_ENABLED.then(read)
+}
+
+fn read() -> MemoryStats {
+    MemoryStats {
+        peak_bytes: PEAK
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE.load(Ordering::Relaxed)),
+        total_bytes: TOTAL.load(Ordering::Relaxed),
+        allocations: COUNT.load(Ordering::Relaxed),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Heap usage of a single run of a solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Highest amount of memory allocated at the same time, on top of what was allocated before the run.
+    pub peak_bytes: u64,
+    /// Sum of the sizes of all allocations.
+    pub total_bytes: u64,
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+}
+
+impl MemoryStats {
+    /// Combines the usage of two runs, e.g. both parts of a day.
+    pub fn combine(&self, other: &Self) -> Self {
+        Self {
+            peak_bytes: self.peak_bytes.max(other.peak_bytes),
+            total_bytes: self.total_bytes + other.total_bytes,
+            allocations: self.allocations + other.allocations,
+        }
+    }
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} in {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.allocations
+        )
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+impl From<&MemoryStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: number("peak_bytes")?,
+            total_bytes: number("total_bytes")?,
+            allocations: number("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, MemoryStats};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn combines_parts() {
+        let part_1 = MemoryStats {
+            peak_bytes: 100,
+            total_bytes: 300,
+            allocations: 3,
+        };
+        let part_2 = MemoryStats {
+            peak_bytes: 50,
+            total_bytes: 50,
+            allocations: 1,
+        };
+
+        assert_eq!(
+            part_1.combine(&part_2),
+            MemoryStats {
+                peak_bytes: 100,
+                total_bytes: 350,
+                allocations: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = MemoryStats {
+            peak_bytes: 1024,
+            total_bytes: 4096,
+            allocations: 12,
+        };
+        let json = JsonValue::from(&stats);
+        assert_eq!(MemoryStats::try_from(&json).unwrap(), stats);
+    }
+
+    // with the allocator installed, allocations of concurrently running tests would leak into the counters.
+    #[cfg(not(feature = "count-allocs"))]
+    #[test]
+    fn counts_allocations() {
+        use super::{read, reset, CountingAllocator};
+        use std::alloc::{GlobalAlloc, Layout};
+
+        reset();
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        unsafe {
+            let ptr = CountingAllocator.alloc(layout);
+            let ptr = CountingAllocator.realloc(ptr, layout, 128);
+            CountingAllocator.dealloc(ptr, Layout::from_size_align(128, 8).unwrap());
+        }
+
+        assert_eq!(
+            read(),
+            MemoryStats {
+                peak_bytes: 128,
+                total_bytes: 192,
+                allocations: 2,
+            }
+        );
+    }
+}
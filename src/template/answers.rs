@@ -0,0 +1,250 @@
+/// Module that stores verified answers, so solutions can be checked against them after a refactor.
+use std::{
+    collections::BTreeMap,
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, Day};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Outcome of checking an answer against the stored one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Correct,
+    Incorrect,
+    Unknown,
+}
+
+/// Answers per part, keyed by a day or, for the answers of an inputs directory, by a file name.
+pub(crate) type AnswerEntries = HashMap<String, [Option<String>; 2]>;
+
+/// Verified answers per day and part.
+/// Serialized as `{ "01": { "part_1": "11", "part_2": "31" } }`, which is easy to edit by hand.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    data: BTreeMap<Day, [Option<String>; 2]>,
+}
+
+impl Answers {
+    /// Read answers from the answers file. If not present, returns empty answers.
+    /// A file that cannot be read or parsed is an error, so it is never overwritten with fewer answers.
+    pub fn read_from_file(year: Option<u16>) -> Result<Self, String> {
+        let path = data_dir(year).join(ANSWERS_FILE_NAME);
+
+        read(&path).and_then(|entries| {
+            Answers::from_entries(entries)
+                .map_err(|e| format!("could not parse \"{}\": {e}", path.display()))
+        })
+    }
+
+    fn from_entries(entries: AnswerEntries) -> Result<Self, String> {
+        let mut answers = Answers::default();
+
+        for (day, parts) in entries {
+            let day = Day::from_str(&day).map_err(|e| format!("{day}: {e}"))?;
+            for (part, answer) in (1..=2).zip(&parts) {
+                if let Some(answer) = answer {
+                    answers.set(day, part, answer);
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn store_file(&self, year: Option<u16>) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(data_dir(year).join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let index = part_index(part)?;
+        self.data.get(&day)?[index].as_deref()
+    }
+
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        if let Some(index) = part_index(part) {
+            self.data.entry(day).or_default()[index] = Some(answer.trim().to_string());
+        }
+    }
+
+    /// Compare `answer` against the stored answer for a part.
+    pub fn check(&self, day: Day, part: u8, answer: Option<&str>) -> CheckStatus {
+        match (self.get(day, part), answer) {
+            (None, _) => CheckStatus::Unknown,
+            (Some(expected), Some(answer)) if expected == answer.trim() => CheckStatus::Correct,
+            (Some(_), _) => CheckStatus::Incorrect,
+        }
+    }
+}
+
+/// Read an answers file. If not present, returns no answers.
+/// A file that cannot be read or parsed is an error, so it is never mistaken for missing answers.
+pub(crate) fn read(path: &Path) -> Result<AnswerEntries, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => {
+            parse(&contents).map_err(|e| format!("could not parse \"{}\": {e}", path.display()))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(AnswerEntries::default()),
+        Err(e) => Err(format!("could not read \"{}\": {e}", path.display())),
+    }
+}
+
+/// Parse `{ "<key>": { "part_1": "11", "part_2": "31" } }`.
+fn parse(s: &str) -> Result<AnswerEntries, String> {
+    let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+    let entries = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?;
+
+    entries
+        .iter()
+        .map(|(key, parts)| {
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("{key}: expected answers to be an object."))?;
+
+            let mut answers: [Option<String>; 2] = Default::default();
+            for (i, answer) in answers.iter_mut().enumerate() {
+                *answer = match parts.get(&format!("part_{}", i + 1)) {
+                    None => None,
+                    Some(v) if v.is_null() => None,
+                    // answers may be written as plain numbers when edited by hand.
+                    Some(JsonValue::Number(n)) => Some(n.to_string()),
+                    Some(JsonValue::String(s)) => Some(s.clone()),
+                    Some(_) => {
+                        return Err(format!("{key}: expected answer to be a string or number."))
+                    }
+                };
+            }
+
+            Ok((key.clone(), answers))
+        })
+        .collect()
+}
+
+pub(crate) fn part_index(part: u8) -> Option<usize> {
+    match part {
+        1 => Some(0),
+        2 => Some(1),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let map = value
+            .data
+            .iter()
+            .map(|(day, parts)| {
+                let parts: HashMap<String, JsonValue> = parts
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, answer)| {
+                        let answer = answer.as_ref()?;
+                        Some((format!("part_{}", i + 1), JsonValue::String(answer.clone())))
+                    })
+                    .collect();
+                (day.to_string(), JsonValue::Object(parts))
+            })
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse(&value).and_then(Answers::from_entries)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, read, Answers, CheckStatus};
+    use crate::day;
+    use std::{env, fs, process};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn checks_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "11");
+
+        assert_eq!(answers.check(day!(1), 1, Some("11")), CheckStatus::Correct);
+        assert_eq!(
+            answers.check(day!(1), 1, Some("12")),
+            CheckStatus::Incorrect
+        );
+        assert_eq!(answers.check(day!(1), 1, None), CheckStatus::Incorrect);
+        assert_eq!(answers.check(day!(1), 2, Some("31")), CheckStatus::Unknown);
+        assert_eq!(answers.check(day!(2), 1, Some("11")), CheckStatus::Unknown);
+    }
+
+    #[test]
+    fn handles_hand_written_json() {
+        let json = r#"{ "01": { "part_1": 11, "part_2": "abc" }, "03": { "part_2": null } }"#;
+        let answers = Answers::try_from(json.to_string()).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("11"));
+        assert_eq!(answers.get(day!(1), 2), Some("abc"));
+        assert_eq!(answers.get(day!(3), 2), None);
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "11");
+        answers.set(day!(25), 2, "multi\nline");
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn reports_unparsable_files() {
+        let dir = env::temp_dir().join(format!("aoc-answers-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("answers.json");
+        fs::write(&path, r#"{ "01": { "part_1": "11" "#).unwrap();
+        let result = read(&path);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.unwrap_err().starts_with("could not parse"));
+        assert!(read(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parses_answers_by_file_name() {
+        let json =
+            r#"{ "alice.txt": { "part_1": 11, "part_2": "abc" }, "bob.txt": { "part_2": null } }"#;
+        let entries = parse(json).unwrap();
+
+        assert_eq!(
+            entries["alice.txt"],
+            [Some("11".into()), Some("abc".into())]
+        );
+        assert_eq!(entries["bob.txt"], [None, None]);
+        assert!(parse("[]").is_err());
+        assert!(parse(r#"{ "alice.txt": { "part_1": [] } }"#).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_days() {
+        Answers::try_from(r#"{ "26": { "part_1": "1" } }"#.to_string()).unwrap();
+    }
+}
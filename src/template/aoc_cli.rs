@@ -1,40 +1,88 @@
 /// Wrapper module around the "aoc-cli" command-line.
-use std::{
-    fmt::Display,
-    process::{Command, Output, Stdio},
-};
+/// With the `aoc-client` feature, commands are served by the built-in HTTP client instead.
+use std::{fmt::Display, process::Output};
 
-use crate::template::Day;
+#[cfg(not(feature = "aoc-client"))]
+use std::process::{Command, Stdio};
+
+#[cfg(feature = "aoc-client")]
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::{data_dir, Puzzle};
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    #[cfg(feature = "aoc-client")]
+    Client(AocClientError),
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
+            AocCommandError::CommandNotFound => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            #[cfg(feature = "aoc-client")]
+            AocCommandError::Client(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl AocCommandError {
+    /// Whether aoc-cli is not installed, or the built-in client is not configured.
+    pub fn is_unavailable(&self) -> bool {
+        match self {
+            AocCommandError::CommandNotFound => true,
+            #[cfg(feature = "aoc-client")]
+            AocCommandError::Client(
+                AocClientError::MissingSession | AocClientError::MissingYear,
+            ) => true,
+            _ => false,
         }
     }
 }
 
+#[cfg(feature = "aoc-client")]
+impl From<AocClientError> for AocCommandError {
+    fn from(value: AocClientError) -> Self {
+        AocCommandError::Client(value)
+    }
+}
+
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
-        .arg("-V")
-        .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
+    #[cfg(feature = "aoc-client")]
+    {
+        AocClient::check()?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "aoc-client"))]
+    {
+        Command::new("aoc")
+            .arg("-V")
+            .output()
+            .map_err(|_| AocCommandError::CommandNotFound)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "aoc-client")]
+pub fn read(puzzle: Puzzle) -> Result<(), AocCommandError> {
+    let description = AocClient::from_env(puzzle.year)?.fetch_puzzle(puzzle.day)?;
+    println!("{description}");
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+#[cfg(not(feature = "aoc-client"))]
+pub fn read(puzzle: Puzzle) -> Result<(), AocCommandError> {
+    let puzzle_path = get_puzzle_path(puzzle);
 
     let args = build_args(
         "read",
@@ -43,15 +91,36 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path,
         ],
-        day,
+        puzzle,
     );
 
-    call_aoc_cli(&args)
+    call_aoc_cli(&args)?;
+    Ok(())
+}
+
+#[cfg(feature = "aoc-client")]
+pub fn download(puzzle: Puzzle) -> Result<(), AocCommandError> {
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    let client = AocClient::from_env(puzzle.year)?;
+    let input = client.fetch_input(puzzle.day)?;
+    let description = client.fetch_puzzle(puzzle.day)?;
+
+    create_parent_dirs(&[&input_path, &puzzle_path]);
+    std::fs::write(&input_path, input).map_err(AocClientError::Io)?;
+    std::fs::write(&puzzle_path, format!("{description}\n")).map_err(AocClientError::Io)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+#[cfg(not(feature = "aoc-client"))]
+pub fn download(puzzle: Puzzle) -> Result<(), AocCommandError> {
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
+    create_parent_dirs(&[&input_path, &puzzle_path]);
 
     let args = build_args(
         "download",
@@ -62,52 +131,97 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path.to_string(),
         ],
-        day,
+        puzzle,
     );
 
-    let output = call_aoc_cli(&args)?;
+    call_aoc_cli(&args)?;
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    Ok(())
+}
+
+/// Submit an answer and return the response of the server as text.
+#[cfg(feature = "aoc-client")]
+pub fn submit(puzzle: Puzzle, part: u8, result: &str) -> Result<String, AocCommandError> {
+    let response = AocClient::from_env(puzzle.year)?.submit(puzzle.day, part, result)?;
+    println!("{response}");
+    Ok(response)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submit an answer and return the response of the server as text.
+#[cfg(not(feature = "aoc-client"))]
+pub fn submit(puzzle: Puzzle, part: u8, result: &str) -> Result<String, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], puzzle);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // capture stdout so callers can tell whether the answer was accepted.
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    print!("{stdout}");
+
+    if output.status.success() {
+        Ok(stdout)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
+
+fn get_input_path(puzzle: Puzzle) -> String {
+    puzzle.data_path("inputs").to_string_lossy().to_string()
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+fn get_puzzle_path(puzzle: Puzzle) -> String {
+    let path = data_dir(puzzle.year)
+        .join("puzzles")
+        .join(format!("{}.md", puzzle.day));
+    path.to_string_lossy().to_string()
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+/// The data folders of a year do not exist until its first day is scaffolded or downloaded.
+fn create_parent_dirs(paths: &[&str]) {
+    for path in paths {
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            // errors surface when the files are written.
+            let _ = std::fs::create_dir_all(parent);
+        }
+    }
 }
 
-fn get_year() -> Option<u16> {
+pub(crate) fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
     }
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+#[cfg(not(feature = "aoc-client"))]
+fn build_args(command: &str, args: &[String], puzzle: Puzzle) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = puzzle.year.or_else(get_year) {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
 
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--day".into(),
+        puzzle.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
 
+#[cfg(not(feature = "aoc-client"))]
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
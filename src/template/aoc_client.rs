@@ -0,0 +1,364 @@
+/// Built-in Advent of Code HTTP client, used instead of aoc-cli when the `aoc-client` feature is enabled.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::html::decode_entities;
+use crate::template::{aoc_cli, Day};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    MissingYear,
+    Status(u16),
+    Transport(String),
+    Io(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or create the file \"~/.adventofcode.session\"."
+            ),
+            AocClientError::MissingYear => write!(f, "AOC_YEAR is not set."),
+            AocClientError::Status(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(value: io::Error) -> Self {
+        AocClientError::Io(value)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(value: ureq::Error) -> Self {
+        match value {
+            ureq::Error::Status(status, _) => AocClientError::Status(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        AocClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+            agent,
+        }
+    }
+
+    /// Configure a client from the environment:
+    ///  - `AOC_SESSION` or the file `~/.adventofcode.session` for the session cookie.
+    ///  - `AOC_YEAR` for the puzzle year, unless `year` is given.
+    ///  - `AOC_BASE_URL` to talk to a server other than adventofcode.com.
+    pub fn from_env(year: Option<u16>) -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        let year = year
+            .or_else(aoc_cli::get_year)
+            .ok_or(AocClientError::MissingYear)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(AocClient::new(&base_url, &session, year))
+    }
+
+    /// Verify that a session cookie is configured.
+    pub fn check() -> Result<(), AocClientError> {
+        read_session()
+            .map(|_| ())
+            .ok_or(AocClientError::MissingSession)
+    }
+
+    pub fn fetch_input(&self, day: Day) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.day_url(day, "/input"))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// The puzzle description of `day`, converted to markdown.
+    pub fn fetch_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.day_url(day, ""))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        Ok(articles_to_markdown(&response.into_string()?))
+    }
+
+    /// Submit an answer and return the server's response as text.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .post(&self.day_url(day, "/answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer.trim())])?;
+        Ok(articles_to_markdown(&response.into_string()?))
+    }
+
+    fn day_url(&self, day: Day, path: &str) -> String {
+        format!(
+            "{}/{}/day/{}{path}",
+            self.base_url,
+            self.year,
+            day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn read_session() -> Option<String> {
+    let session = env::var("AOC_SESSION").ok().or_else(|| {
+        let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+        fs::read_to_string(PathBuf::from(home).join(".adventofcode.session")).ok()
+    })?;
+
+    let session = session.trim();
+    (!session.is_empty()).then(|| session.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Converts every `<article>` element of an Advent of Code page to markdown.
+fn articles_to_markdown(html: &str) -> String {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let article = &rest[start..];
+        let Some(content_start) = article.find('>') else {
+            break;
+        };
+        let content_end = article.find("</article>").unwrap_or(article.len());
+
+        if content_start < content_end {
+            articles.push(html_to_markdown(&article[content_start + 1..content_end]));
+        }
+
+        rest = &article[content_end..];
+        rest = rest.strip_prefix("</article>").unwrap_or(rest);
+    }
+
+    articles.join("\n\n")
+}
+
+/// Converts the small subset of HTML used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) => out.push('*'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            ("li", false) => out.push_str("- "),
+            ("li" | "ul", true) => out.push('\n'),
+            ("br", _) => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    out.trim().to_string()
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    // whitespace between block elements is layout, not content.
+    if !in_pre && text.trim().is_empty() && text.contains('\n') {
+        return;
+    }
+
+    out.push_str(&decode_entities(text));
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + len]))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles_to_markdown, AocClient, AocClientError};
+    use crate::day;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serve a single request on a local port and return the raw request that was received.
+    fn mock_server(status: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut buf = [0; 1024];
+
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+
+                if n == 0 {
+                    break;
+                }
+            }
+
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (base_url, server) = mock_server("200 OK", "1 2 3\n");
+        let client = AocClient::new(&base_url, "secret\n", 2024);
+
+        assert_eq!(client.fetch_input(day!(1)).unwrap(), "1 2 3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let body = "<main><article><p>That's the right answer!</p></article></main>";
+        let (base_url, server) = mock_server("200 OK", body);
+        let client = AocClient::new(&base_url, "secret", 2023);
+
+        assert_eq!(
+            client.submit(day!(12), 2, "42").unwrap(),
+            "That's the right answer!"
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/12/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn maps_error_statuses() {
+        let (base_url, server) = mock_server("404 Not Found", "");
+        let client = AocClient::new(&base_url, "secret", 2024);
+
+        assert!(matches!(
+            client.fetch_puzzle(day!(1)),
+            Err(AocClientError::Status(404))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Read <a href="/2024/about">this</a> &amp; <em>that</em>.</p>
+<pre><code>a &lt; b
+c
+</code></pre>
+<ul>
+<li>The answer is <code><em>11</em></code>.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>11</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>More.</p></article>
+</main>"#;
+
+        let expected = "## --- Day 1: Test ---\n\nRead [this](/2024/about) & *that*.\n\n```\na < b\nc\n```\n\n- The answer is `*11*`.\n\n## --- Part Two ---\n\nMore.";
+        assert_eq!(articles_to_markdown(html), expected);
+    }
+}
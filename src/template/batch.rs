@@ -0,0 +1,161 @@
+/// Runs one day over every input file in a directory, e.g. to compare solutions across personal inputs.
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs, process};
+
+use crate::template::answers::{self, Answers, CheckStatus};
+use crate::template::commands::CommandError;
+use crate::template::runner::{PartResult, PartStatus, RunOptions};
+use crate::template::{Puzzle, ANSI_BOLD, ANSI_RESET};
+
+/// Name of the optional file in an inputs directory that holds the expected answers per input file.
+static EXPECTED_FILE_NAME: &str = "answers.json";
+
+/// The directory passed with `--inputs-dir`, if any.
+pub fn inputs_dir_from_args() -> Option<PathBuf> {
+    let args: Vec<String> = env::args().collect();
+    let i = args.iter().position(|x| x == "--inputs-dir")?;
+
+    match args.get(i + 1) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => {
+            CommandError::Usage("unexpected command-line input. Format: --inputs-dir <dir>".into())
+                .exit()
+        }
+    }
+}
+
+/// Run `run` over every input file in `dir` and print a table of the results.
+/// Exits with a non-zero status if any answer does not match its expected answer or a part failed.
+pub fn run_inputs_dir(dir: &Path, puzzle: Puzzle, run: fn(&str, &RunOptions) -> Vec<PartResult>) {
+    let files = input_files(dir).unwrap_or_else(|e| {
+        CommandError::Io(
+            format!("could not read inputs directory \"{}\"", dir.display()),
+            e,
+        )
+        .exit()
+    });
+
+    let expected = answers::read(&dir.join(EXPECTED_FILE_NAME))
+        .unwrap_or_else(|e| CommandError::Failed(e).exit());
+    let own_input = fs::read_to_string(puzzle.data_path("inputs")).ok();
+    let stored_answers =
+        Answers::read_from_file(puzzle.year).unwrap_or_else(|e| CommandError::Failed(e).exit());
+
+    let options = RunOptions {
+        is_timed: env::args().any(|x| x == "--time"),
+        ..RunOptions::default()
+    };
+
+    let mut rows = vec![];
+
+    for (i, path) in files.iter().enumerate() {
+        let name = file_name(path);
+
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}{name}{ANSI_RESET}");
+
+        let input = match fs::read_to_string(path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("could not read input: {e}");
+                continue;
+            }
+        };
+
+        let mut results = run(&input, &options);
+
+        for result in &mut results {
+            // the day's own input is checked against the answers accepted by the server.
+            let expected_answer = expected
+                .get(&name)
+                .zip(answers::part_index(result.part))
+                .and_then(|(parts, i)| parts[i].as_deref())
+                .or_else(|| {
+                    (own_input.as_deref() == Some(input.as_str()))
+                        .then(|| stored_answers.get(puzzle.day, result.part))
+                        .flatten()
+                });
+
+            result.check = Some(match (expected_answer, result.answer.as_deref()) {
+                (None, _) => CheckStatus::Unknown,
+                (Some(expected), Some(answer)) if expected.trim() == answer.trim() => {
+                    CheckStatus::Correct
+                }
+                (Some(_), _) => CheckStatus::Incorrect,
+            });
+        }
+
+        rows.push((name, results));
+    }
+
+    println!();
+    print_table(&rows);
+
+    let has_failed = rows.iter().flat_map(|(_, results)| results).any(|r| {
+        r.check == Some(CheckStatus::Incorrect)
+            || matches!(r.status, PartStatus::Failed | PartStatus::Panicked)
+    });
+
+    if has_failed {
+        process::exit(CommandError::SolutionFailed.exit_code());
+    }
+}
+
+/// Input files of `dir`, sorted by name. The expected answers file is skipped.
+fn input_files(dir: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && file_name(path) != EXPECTED_FILE_NAME)
+        .collect();
+
+    files.sort();
+    Ok(files)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn print_table(rows: &[(String, Vec<PartResult>)]) {
+    println!("File | Part 1 | Part 2");
+
+    for (name, results) in rows {
+        let columns: Vec<String> = [1, 2]
+            .iter()
+            .map(|part| {
+                results
+                    .iter()
+                    .find(|r| r.part == *part)
+                    .map_or_else(|| "-".into(), format_cell)
+            })
+            .collect();
+
+        println!("{name} | {} | {}", columns[0], columns[1]);
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_cell(result: &PartResult) -> String {
+    let answer = match result.answer.as_deref() {
+        _ if result.status == PartStatus::Failed => "✖ error",
+        _ if result.status == PartStatus::Panicked => "✖ panicked",
+        Some(answer) if answer.contains('\n') => "▼",
+        Some(answer) => answer,
+        None => "✖",
+    };
+
+    let check = match result.check {
+        Some(CheckStatus::Correct) => " ✔",
+        Some(CheckStatus::Incorrect) => " ✘",
+        _ => "",
+    };
+
+    let duration = Duration::from_nanos(result.nanos as u64);
+    format!("{answer}{check} ({duration:.1?})")
+}
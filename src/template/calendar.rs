@@ -0,0 +1,216 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::template::{aoc_cli, AllDays, Day};
+
+/// The highest day number of any event.
+pub const MAX_DAYS: u8 = 25;
+
+/// Offset of the Advent of Code server time (EST) to UTC, in hours. Puzzles unlock at midnight server time.
+const SERVER_UTC_OFFSET: i64 = -5;
+
+/// Source of the current time, in seconds since the unix epoch.
+pub trait Clock {
+    fn now(&self) -> u64;
+}
+
+/// The wall clock of the system.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs())
+    }
+}
+
+/// A clock that is stopped at a given unix timestamp.
+#[derive(Clone, Copy, Debug)]
+pub struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn now(&self) -> u64 {
+        self.0
+    }
+}
+
+/// December 1st 2024, 05:00 UTC, when the first puzzle of the 2024 event unlocked.
+#[cfg(feature = "test_lib")]
+pub(crate) const DEC_1_2024: u64 = 1_733_029_200;
+
+/// The schedule of a single event: how many days it has and when each of them unlocks.
+///
+/// ```
+/// # use advent_of_code::template::{Calendar, FixedClock};
+/// let calendar = Calendar::new(2024);
+/// assert_eq!(calendar.day_count(), 25);
+/// assert_eq!(Calendar::new(2025).day_count(), 12);
+///
+/// // December 1st 2024, 05:00 UTC.
+/// let clock = FixedClock(1_733_029_200);
+/// assert_eq!(calendar.today(&clock).map(|d| d.into_inner()), Some(1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Calendar {
+    year: u16,
+}
+
+impl Calendar {
+    pub const fn new(year: u16) -> Self {
+        Self { year }
+    }
+
+    /// The calendar of `year`. Without a year, the event set in `AOC_YEAR` or else the most recent one is used.
+    pub fn resolve(year: Option<u16>) -> Self {
+        year.or_else(aoc_cli::get_year)
+            .map_or_else(|| Self::latest(&SystemClock), Self::new)
+    }
+
+    /// The most recent event that has started at the time of `clock`.
+    pub fn latest(clock: &impl Clock) -> Self {
+        let (year, month, _) = civil_from_timestamp(server_time(clock.now()));
+        Self::new(if month == 12 { year } else { year - 1 })
+    }
+
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Number of days of the event. Events since 2025 are 12 days long.
+    pub const fn day_count(&self) -> u8 {
+        if self.year >= 2025 {
+            12
+        } else {
+            MAX_DAYS
+        }
+    }
+
+    pub const fn contains(&self, day: Day) -> bool {
+        day.into_inner() <= self.day_count()
+    }
+
+    /// Every day of the event, in order.
+    pub fn days(&self) -> AllDays {
+        AllDays::until(self.day_count())
+    }
+
+    /// Unix timestamp at which the puzzle of `day` unlocks.
+    pub fn unlock_time(&self, day: Day) -> u64 {
+        let days = days_from_civil(i64::from(self.year), 12, i64::from(day.into_inner()));
+        let timestamp = days * 86_400 - SERVER_UTC_OFFSET * 3600;
+        u64::try_from(timestamp).unwrap_or(0)
+    }
+
+    pub fn is_unlocked(&self, day: Day, clock: &impl Clock) -> bool {
+        self.contains(day) && clock.now() >= self.unlock_time(day)
+    }
+
+    /// The next day that unlocks after the time of `clock`, with its unlock time.
+    pub fn next_unlock(&self, clock: &impl Clock) -> Option<(Day, u64)> {
+        self.days()
+            .map(|day| (day, self.unlock_time(day)))
+            .find(|(_, time)| *time > clock.now())
+    }
+
+    /// The day whose puzzle unlocked on the current day in server time, if the event is running.
+    pub fn today(&self, clock: &impl Clock) -> Option<Day> {
+        let now = clock.now();
+        self.days().find(|day| {
+            let unlock = self.unlock_time(*day);
+            now >= unlock && now < unlock + 86_400
+        })
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn server_time(timestamp: u64) -> i64 {
+    timestamp as i64 + SERVER_UTC_OFFSET * 3600
+}
+
+/// Days since the unix epoch of a date, see: https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Year, month and day of a unix timestamp, see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn civil_from_timestamp(timestamp: i64) -> (u16, u8, u8) {
+    let days = timestamp.div_euclid(86_400) + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year as u16, month as u8, day as u8)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Calendar, FixedClock, DEC_1_2024};
+    use crate::day;
+
+    #[test]
+    fn counts_days_per_event() {
+        assert_eq!(Calendar::new(2015).days().count(), 25);
+        assert_eq!(Calendar::new(2024).days().count(), 25);
+        assert_eq!(Calendar::new(2025).days().count(), 12);
+        assert!(Calendar::new(2025).contains(day!(12)));
+        assert!(!Calendar::new(2025).contains(day!(13)));
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        let calendar = Calendar::new(2024);
+        assert_eq!(calendar.unlock_time(day!(1)), DEC_1_2024);
+        assert_eq!(calendar.unlock_time(day!(25)), DEC_1_2024 + 24 * 86_400);
+
+        assert!(!calendar.is_unlocked(day!(1), &FixedClock(DEC_1_2024 - 1)));
+        assert!(calendar.is_unlocked(day!(1), &FixedClock(DEC_1_2024)));
+        assert!(!Calendar::new(2025).is_unlocked(day!(13), &FixedClock(u64::MAX)));
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        let calendar = Calendar::new(2024);
+        assert_eq!(
+            calendar.next_unlock(&FixedClock(0)),
+            Some((day!(1), DEC_1_2024))
+        );
+        assert_eq!(
+            calendar.next_unlock(&FixedClock(DEC_1_2024)),
+            Some((day!(2), DEC_1_2024 + 86_400))
+        );
+        assert_eq!(calendar.next_unlock(&FixedClock(u64::MAX)), None);
+    }
+
+    #[test]
+    fn finds_today() {
+        let calendar = Calendar::new(2024);
+        assert_eq!(calendar.today(&FixedClock(DEC_1_2024 - 1)), None);
+        assert_eq!(calendar.today(&FixedClock(DEC_1_2024)), Some(day!(1)));
+        assert_eq!(
+            calendar.today(&FixedClock(DEC_1_2024 + 86_400 - 1)),
+            Some(day!(1))
+        );
+        assert_eq!(calendar.today(&FixedClock(DEC_1_2024 + 25 * 86_400)), None);
+    }
+
+    #[test]
+    fn finds_latest_event() {
+        assert_eq!(Calendar::latest(&FixedClock(DEC_1_2024)).year(), 2024);
+        assert_eq!(Calendar::latest(&FixedClock(DEC_1_2024 - 1)).year(), 2023);
+        // January 1st 2025.
+        assert_eq!(Calendar::latest(&FixedClock(1_735_707_600)).year(), 2024);
+    }
+}
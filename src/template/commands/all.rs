@@ -1,5 +1,25 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::commands::CommandError;
+use crate::template::{run_multi::run_multi, Calendar};
+
+pub fn handle(
+    year: Option<u16>,
+    is_release: bool,
+    jobs: usize,
+    check: bool,
+    timeout: Option<Duration>,
+) -> Result<(), CommandError> {
+    let days = Calendar::resolve(year).days().collect();
+    let summary = run_multi(year, &days, is_release, false, jobs, check, timeout);
+
+    if summary.failed_checks > 0
+        || summary.timed_out > 0
+        || summary.failed > 0
+        || summary.panicked > 0
+    {
+        return Err(CommandError::SolutionFailed);
+    }
+
+    Ok(())
 }
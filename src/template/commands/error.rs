@@ -0,0 +1,143 @@
+/// The error shared by all command handlers.
+///
+/// `main` prints the error and exits with its [`CommandError::exit_code`], so scripts wrapping the
+/// commands can tell the kinds of failures apart. Solution bins exit with the same codes.
+use std::fmt::Display;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+
+use crate::template::aoc_cli::AocCommandError;
+
+#[derive(Debug)]
+pub enum CommandError {
+    /// A solution did not succeed: a part failed or panicked, an answer did not match, a day
+    /// timed out or the solution did not build. Its output has already been printed.
+    SolutionFailed,
+    /// The arguments of the command are invalid.
+    Usage(String),
+    /// aoc-cli is not installed, or the built-in client has no session or year configured.
+    AocUnavailable(AocCommandError),
+    /// aoc-cli or the Advent of Code server failed.
+    Aoc(AocCommandError),
+    /// A file already exists and would be overwritten.
+    FileExists(PathBuf),
+    /// Reading or writing a file, or starting a process, failed.
+    Io(String, io::Error),
+    /// `cargo time --compare` found parts that got slower than the threshold.
+    Regression,
+    /// The command could not complete for another reason.
+    Failed(String),
+    /// A solution bin stopped with one of the other errors, e.g. a missing input or a failed submission.
+    /// Its message has already been printed, and its exit code is passed on.
+    SolutionExited(i32),
+}
+
+impl CommandError {
+    /// Exit status of the process for this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CommandError::SolutionFailed => 1,
+            CommandError::Usage(_) => 2,
+            CommandError::AocUnavailable(_) => 3,
+            CommandError::Aoc(_) => 4,
+            CommandError::FileExists(_) => 5,
+            CommandError::Io(..) => 6,
+            CommandError::Regression => 7,
+            CommandError::Failed(_) => 8,
+            CommandError::SolutionExited(code) => *code,
+        }
+    }
+
+    /// Print the error and exit the process with its [`CommandError::exit_code`].
+    pub fn exit(self) -> ! {
+        eprintln!("Error: {self}");
+        process::exit(self.exit_code())
+    }
+
+    /// Wraps an I/O error with a description of what failed, e.g. `"failed to write example file"`.
+    pub fn io(context: impl Into<String>) -> impl FnOnce(io::Error) -> Self {
+        let context = context.into();
+        move |e| CommandError::Io(context, e)
+    }
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandError::SolutionFailed => write!(f, "the solution did not succeed."),
+            CommandError::Usage(message) | CommandError::Failed(message) => {
+                write!(f, "{message}")
+            }
+            CommandError::AocUnavailable(e) | CommandError::Aoc(e) => write!(f, "{e}"),
+            CommandError::FileExists(path) => write!(
+                f,
+                "\"{}\" already exists, append --overwrite to replace it.",
+                path.display()
+            ),
+            CommandError::Io(context, e) => write!(f, "{context}: {e}"),
+            CommandError::Regression => write!(f, "benchmarks regressed beyond the threshold."),
+            CommandError::SolutionExited(code) => {
+                write!(f, "the solution exited with status {code}.")
+            }
+        }
+    }
+}
+
+impl From<AocCommandError> for CommandError {
+    fn from(value: AocCommandError) -> Self {
+        if value.is_unavailable() {
+            CommandError::AocUnavailable(value)
+        } else {
+            CommandError::Aoc(value)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+    use std::io;
+
+    use super::CommandError;
+    use crate::template::aoc_cli::AocCommandError;
+
+    #[test]
+    fn maps_errors_to_distinct_exit_codes() {
+        let errors = [
+            CommandError::SolutionFailed,
+            CommandError::Usage("usage".into()),
+            CommandError::AocUnavailable(AocCommandError::CommandNotFound),
+            CommandError::Aoc(AocCommandError::CommandNotCallable),
+            CommandError::FileExists("src/bin/01.rs".into()),
+            CommandError::Io("failed".into(), io::ErrorKind::NotFound.into()),
+            CommandError::Regression,
+            CommandError::Failed("failed".into()),
+        ];
+
+        let codes: HashSet<i32> = errors.iter().map(CommandError::exit_code).collect();
+        assert_eq!(codes.len(), errors.len());
+        assert!(!codes.contains(&0));
+    }
+
+    #[test]
+    fn passes_on_exit_codes_of_solutions() {
+        let error = CommandError::SolutionExited(6);
+        assert_eq!(error.exit_code(), 6);
+        assert_eq!(error.to_string(), "the solution exited with status 6.");
+    }
+
+    #[test]
+    fn separates_missing_aoc_cli() {
+        assert_eq!(
+            CommandError::from(AocCommandError::CommandNotFound).exit_code(),
+            3
+        );
+        assert_eq!(
+            CommandError::from(AocCommandError::CommandNotCallable).exit_code(),
+            4
+        );
+    }
+}
@@ -0,0 +1,410 @@
+/// Extracts the examples and their expected answers from a downloaded puzzle description.
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+
+use crate::template::commands::CommandError;
+use crate::template::example_manifest::{self, Example};
+use crate::template::html::decode_entities;
+use crate::template::{data_dir, Puzzle};
+
+/// An example block of a puzzle description.
+#[derive(Debug, PartialEq, Eq)]
+struct Block {
+    /// The part whose description contains the block.
+    part: u8,
+    text: String,
+}
+
+/// Example blocks and expected answers of a puzzle description.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Examples {
+    blocks: Vec<Block>,
+    /// The last emphasized code of each part's description, which is the answer for its example.
+    answers: [Option<String>; 2],
+}
+
+pub fn handle(puzzle: Puzzle, blocks: &[usize], overwrite: bool) -> Result<(), CommandError> {
+    let path = data_dir(puzzle.year)
+        .join("puzzles")
+        .join(format!("{}.md", puzzle.day));
+
+    let markdown = fs::read_to_string(&path).map_err(CommandError::io(format!(
+        "could not read \"{}\", run `cargo download {puzzle}` first",
+        path.display()
+    )))?;
+
+    let examples = parse_examples(&markdown);
+
+    if examples.blocks.is_empty() {
+        return Err(CommandError::Failed(format!(
+            "no example blocks found in \"{}\".",
+            path.display()
+        )));
+    }
+
+    let selected = select_blocks(&examples.blocks, blocks).map_err(CommandError::Usage)?;
+
+    let mut files: Vec<(usize, u8)> = vec![];
+
+    for (n, index) in selected.iter().enumerate() {
+        let block = &examples.blocks[*index];
+        let n = u8::try_from(n + 1).unwrap_or(u8::MAX);
+        let path = example_path(puzzle, n);
+
+        let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+        if !is_empty && !overwrite {
+            return Err(CommandError::FileExists(path));
+        }
+
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| fs::write(&path, &block.text))
+            .map_err(CommandError::io("failed to write example file"))?;
+
+        println!(
+            "Wrote block {} (part {}) to \"{}\"",
+            index + 1,
+            block.part,
+            path.display()
+        );
+        files.push((*index, n));
+    }
+
+    // an answer belongs to the first selected block of its part, part 2 falls back to the example of part 1.
+    let mut entries: Vec<Example> = vec![];
+    for part in [1, 2] {
+        let Some(answer) = &examples.answers[usize::from(part - 1)] else {
+            println!("No expected answer found for part {part}.");
+            continue;
+        };
+
+        let Some(n) = files
+            .iter()
+            .find(|(index, _)| examples.blocks[*index].part == part)
+            .or_else(|| files.first())
+            .map(|(_, n)| *n)
+        else {
+            continue;
+        };
+
+        let file = example_file_name(puzzle, n);
+        let i = entries
+            .iter()
+            .position(|e| e.file == file)
+            .unwrap_or_else(|| {
+                entries.push(Example {
+                    file,
+                    ..Example::default()
+                });
+                entries.len() - 1
+            });
+
+        if part == 1 {
+            entries[i].part_1 = Some(answer.clone());
+        } else {
+            entries[i].part_2 = Some(answer.clone());
+        }
+        println!("Expecting {answer} for part {part} of example {n}");
+    }
+
+    append_to_manifest(puzzle, &entries)
+}
+
+/// Append `entries` to the example manifest of the day, skipping files that are already listed.
+fn append_to_manifest(puzzle: Puzzle, entries: &[Example]) -> Result<(), CommandError> {
+    let path = data_dir(puzzle.year)
+        .join("examples")
+        .join(format!("{}.toml", puzzle.day));
+
+    let mut manifest = match fs::read_to_string(&path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        result => result.map_err(CommandError::io(format!(
+            "could not read \"{}\"",
+            path.display()
+        )))?,
+    };
+
+    let skipped = append_examples(&mut manifest, entries).map_err(|e| {
+        CommandError::Failed(format!("could not parse \"{}\": {e}", path.display()))
+    })?;
+
+    for file in &skipped {
+        println!(
+            "\"{file}\" is already listed in \"{}\", leaving it unchanged.",
+            path.display()
+        );
+    }
+
+    let added = entries.len() - skipped.len();
+    if added > 0 {
+        fs::write(&path, manifest).map_err(CommandError::io("failed to write example manifest"))?;
+        println!("Added {added} example(s) to \"{}\"", path.display());
+    }
+
+    Ok(())
+}
+
+/// Append the `entries` whose file is not listed in `manifest` yet, and return the files that were skipped.
+fn append_examples(manifest: &mut String, entries: &[Example]) -> Result<Vec<String>, String> {
+    let listed = example_manifest::parse(manifest)?;
+    let mut skipped = vec![];
+
+    for entry in entries {
+        if listed.iter().any(|e| e.file == entry.file) {
+            skipped.push(entry.file.clone());
+            continue;
+        }
+
+        if !manifest.is_empty() {
+            if !manifest.ends_with('\n') {
+                manifest.push('\n');
+            }
+            manifest.push('\n');
+        }
+        manifest.push_str(&example_manifest::format(entry));
+    }
+
+    Ok(skipped)
+}
+
+fn example_path(puzzle: Puzzle, n: u8) -> PathBuf {
+    data_dir(puzzle.year)
+        .join("examples")
+        .join(example_file_name(puzzle, n))
+}
+
+/// File name of the `n`th example, relative to the examples folder as listed in the manifest.
+fn example_file_name(puzzle: Puzzle, n: u8) -> String {
+    format!("{}-{n}.txt", puzzle.day)
+}
+
+/// Indices of the blocks to extract: those passed with `--block`, a choice at the prompt, or the first block.
+fn select_blocks(blocks: &[Block], requested: &[usize]) -> Result<Vec<usize>, String> {
+    if !requested.is_empty() {
+        return requested
+            .iter()
+            .map(|n| {
+                (1..=blocks.len()).contains(n).then(|| n - 1).ok_or(format!(
+                    "There is no block {n}, expected 1 to {}.",
+                    blocks.len()
+                ))
+            })
+            .collect();
+    }
+
+    if blocks.len() == 1 || !io::stdin().is_terminal() {
+        if blocks.len() > 1 {
+            println!(
+                "Found {} blocks, extracting the first. Pass --block <n> to choose others.",
+                blocks.len()
+            );
+        }
+        return Ok(vec![0]);
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        println!("Block {} (part {}):", i + 1, block.part);
+        for line in block.text.lines().take(5) {
+            println!("  {line}");
+        }
+        if block.text.lines().count() > 5 {
+            println!("  ...");
+        }
+        println!();
+    }
+
+    loop {
+        print!("Blocks to extract, e.g. 1 or 1,3 [1]: ");
+        let _ = io::stdout().flush();
+
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line).unwrap_or(0) == 0 {
+            return Ok(vec![0]);
+        }
+
+        if line.trim().is_empty() {
+            return Ok(vec![0]);
+        }
+
+        let choice: Result<Vec<usize>, _> =
+            line.split(',').map(|n| n.trim().parse::<usize>()).collect();
+
+        match choice {
+            Ok(choice) if choice.iter().all(|n| (1..=blocks.len()).contains(n)) => {
+                return Ok(choice.iter().map(|n| n - 1).collect());
+            }
+            _ => println!("Expected numbers between 1 and {}.", blocks.len()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Find the example blocks and expected answers in a puzzle description.
+/// Understands the fenced code blocks written by `download` as well as `<pre><code>` blocks.
+fn parse_examples(markdown: &str) -> Examples {
+    let mut examples = Examples::default();
+    let mut part = 1;
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if let Some(text) = &mut block {
+            let end = line
+                .find("</code></pre>")
+                .or_else(|| (line.trim() == "```").then_some(0));
+
+            match end {
+                Some(end) => {
+                    text.push_str(&decode_entities(&line[..end]));
+                    let text = block.take().unwrap_or_default();
+                    if !text.trim().is_empty() {
+                        examples.blocks.push(Block {
+                            part,
+                            text: if text.ends_with('\n') {
+                                text
+                            } else {
+                                format!("{text}\n")
+                            },
+                        });
+                    }
+                }
+                None => {
+                    text.push_str(&decode_entities(line));
+                    text.push('\n');
+                }
+            }
+            continue;
+        }
+
+        if line.contains("--- Part Two ---") {
+            part = 2;
+        }
+
+        if line.trim_start().starts_with("```") {
+            block = Some(String::new());
+            continue;
+        }
+
+        if let Some(start) = line.find("<pre><code>") {
+            let rest = &line[start + "<pre><code>".len()..];
+            match rest.find("</code></pre>") {
+                Some(end) => examples.blocks.push(Block {
+                    part,
+                    text: format!("{}\n", decode_entities(&rest[..end])),
+                }),
+                None if rest.is_empty() => block = Some(String::new()),
+                None => block = Some(format!("{}\n", decode_entities(rest))),
+            }
+            continue;
+        }
+
+        if !line.starts_with("Your puzzle answer was") {
+            if let Some(answer) = emphasized_code(line).pop() {
+                examples.answers[usize::from(part - 1)] = Some(answer);
+            }
+        }
+    }
+
+    examples
+}
+
+/// Inline code that is emphasized, e.g. `` `*11*` ``, `` *`11`* `` or `<code><em>11</em></code>`.
+fn emphasized_code(line: &str) -> Vec<String> {
+    let line = line
+        .replace("<code><em>", "`*")
+        .replace("</em></code>", "*`")
+        .replace("<em><code>", "*`")
+        .replace("</code></em>", "`*");
+
+    let mut found = vec![];
+    let mut rest = line.as_str();
+
+    while let Some(start) = rest.find('`') {
+        let after = &rest[start + 1..];
+        let Some(len) = after.find('`') else {
+            break;
+        };
+        let code = &after[..len];
+        let is_wrapped = rest[..start].ends_with('*') && after[len + 1..].starts_with('*');
+
+        if let Some(inner) = code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+            found.push(inner.trim_matches('*').to_string());
+        } else if is_wrapped {
+            found.push(code.to_string());
+        }
+
+        rest = &after[len + 1..];
+    }
+
+    found
+        .into_iter()
+        .map(|s| decode_entities(&s))
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{append_examples, emphasized_code, parse_examples, Block};
+    use crate::template::example_manifest::{self, Example};
+
+    #[test]
+    fn finds_emphasized_code() {
+        assert_eq!(
+            emphasized_code("a `*1*`, b *`2`*, c `3` and <code><em>4</em></code>."),
+            vec!["1", "2", "4"]
+        );
+        assert_eq!(emphasized_code("The answer is `*11*`."), vec!["11"]);
+        assert!(emphasized_code("*not code* and `not emphasized`").is_empty());
+    }
+
+    #[test]
+    fn parses_examples() {
+        let markdown = "## --- Day 1: Test ---\n\nFor example:\n\n```\n3   4\n4   3\n```\n\nAfter `*1*` step, the total is `*11*`.\n\nYour puzzle answer was `1651298`.\n\n## --- Part Two ---\n\n<pre><code>a &lt; b\nc\n</code></pre>\n\nThe result is *`31`*.";
+        let examples = parse_examples(markdown);
+
+        assert_eq!(
+            examples.blocks,
+            vec![
+                Block {
+                    part: 1,
+                    text: "3   4\n4   3\n".into()
+                },
+                Block {
+                    part: 2,
+                    text: "a < b\nc\n".into()
+                },
+            ]
+        );
+        assert_eq!(examples.answers, [Some("11".into()), Some("31".into())]);
+    }
+
+    #[test]
+    fn appends_examples_to_manifests() {
+        let example = |file: &str, part_1: &str| Example {
+            file: file.into(),
+            part_1: Some(part_1.into()),
+            ..Example::default()
+        };
+
+        let mut manifest =
+            String::from("# hand-written\n[[example]]\nfile = \"01-1.txt\"\npart_1 = 11");
+        let skipped = append_examples(
+            &mut manifest,
+            &[example("01-1.txt", "12"), example("01-2.txt", "31")],
+        )
+        .unwrap();
+
+        assert_eq!(skipped, vec!["01-1.txt"]);
+        assert!(manifest.starts_with("# hand-written\n"));
+        assert_eq!(
+            example_manifest::parse(&manifest).unwrap(),
+            vec![example("01-1.txt", "11"), example("01-2.txt", "31")]
+        );
+
+        let mut manifest = String::from("[example]");
+        assert!(append_examples(&mut manifest, &[example("01-1.txt", "11")]).is_err());
+    }
+}
@@ -1,6 +1,11 @@
 pub mod all;
 pub mod download;
+mod error;
+pub mod examples;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod today;
+
+pub use error::CommandError;
@@ -1,10 +1,11 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
 };
 
-use crate::template::Day;
+use crate::template::commands::CommandError;
+use crate::template::Puzzle;
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -19,7 +20,12 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    // the data folders of a year do not exist until its first day is scaffolded.
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,53 +33,40 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: Puzzle, overwrite: bool) -> Result<(), CommandError> {
+    let input_path = puzzle.data_path("inputs");
+    let example_path = puzzle.data_path("examples");
+    let module_path = format!("src/bin/{}.rs", puzzle.bin_name());
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    let day_number = puzzle.day.into_inner();
+    let macro_args = match puzzle.year {
+        Some(year) => format!("{year} / {day_number}"),
+        None => day_number.to_string(),
     };
 
-    match file.write_all(
+    let mut file = safe_create_file(&module_path, overwrite).map_err(|e| {
+        if e.kind() == ErrorKind::AlreadyExists {
+            CommandError::FileExists(module_path.clone().into())
+        } else {
+            CommandError::Io("failed to create module file".into(), e)
+        }
+    })?;
+
+    file.write_all(
         MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+            .replace("%DAY_NUMBER%", &macro_args)
             .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+    )
+    .map_err(CommandError::io("failed to write module contents"))?;
+    println!("Created module file \"{}\"", &module_path);
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
-    }
+    create_file(&input_path).map_err(CommandError::io("failed to create input file"))?;
+    println!("Created empty input file \"{}\"", input_path.display());
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
-    }
+    create_file(&example_path).map_err(CommandError::io("failed to create example file"))?;
+    println!("Created empty example file \"{}\"", example_path.display());
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve {puzzle}` to run your solution.");
+    Ok(())
 }
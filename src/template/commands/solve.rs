@@ -1,9 +1,28 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
-use crate::template::Day;
+use crate::template::commands::CommandError;
+use crate::template::input::InputSource;
+use crate::template::{alloc, aoc_cli};
+use crate::template::{data_dir, Puzzle, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// How often `--watch` checks the watched files for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn handle(
+    puzzle: Puzzle,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    check: bool,
+    input: &InputSource,
+    inputs_dir: Option<&str>,
+) -> Result<(), CommandError> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -12,23 +31,202 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if release {
+            cmd_args.push("--release".to_string());
+        }
+
+        if alloc::IS_ENABLED {
+            cmd_args.extend(["--features".to_string(), "count-allocs".to_string()]);
+        }
     }
 
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
+        // fail before running the solution, so a missing aoc-cli is not reported as a failing solution.
+        aoc_cli::check()?;
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if check {
+        cmd_args.push("--check".to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
+    if let Some(dir) = inputs_dir {
+        cmd_args.push("--inputs-dir".to_string());
+        cmd_args.push(dir.to_string());
+    }
+
+    let status = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(CommandError::io("failed to run cargo"))?;
+
+    // failing parts and checks exit with the code of `SolutionFailed`, panics and build errors with 101.
+    // other errors of the solution, e.g. a missing input, are passed on with their own exit code.
+    match status.code() {
+        Some(0) => Ok(()),
+        Some(code) if code != CommandError::SolutionFailed.exit_code() && code != 101 => {
+            Err(CommandError::SolutionExited(code))
+        }
+        _ => Err(CommandError::SolutionFailed),
+    }
+}
+
+/// Re-run `puzzle` whenever its source, examples or input change, followed by a summary of its tests.
+pub fn watch(
+    puzzle: Puzzle,
+    release: bool,
+    dhat: bool,
+    input: &InputSource,
+) -> Result<(), CommandError> {
+    let mut last_seen: Option<Vec<FileState>> = None;
 
-    cmd.wait().unwrap();
+    loop {
+        let files = watched_files(puzzle, input);
+
+        if last_seen.as_ref() != Some(&files) {
+            // clear the screen and move the cursor to the top left corner.
+            print!("\x1b[2J\x1b[H");
+            let _ = io::stdout().flush();
+
+            // a failing solution is shown by its output, the next change may fix it.
+            match handle(puzzle, release, dhat, None, false, input, None) {
+                Ok(()) | Err(CommandError::SolutionFailed | CommandError::SolutionExited(_)) => {}
+                Err(e) => return Err(e),
+            }
+            println!();
+            print_test_summary(puzzle);
+            println!();
+            println!(
+                "Watching {} and its examples and input for changes. Press Ctrl-C to stop.",
+                puzzle.bin_path()
+            );
+
+            // files that changed while the day was running trigger the next run.
+            last_seen = Some(files);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// A watched file and its modification time and size, or `None` if it does not exist.
+type FileState = (PathBuf, Option<(SystemTime, u64)>);
+
+/// The source of `puzzle`, all of its examples and the input it runs against.
+fn watched_files(puzzle: Puzzle, input: &InputSource) -> Vec<FileState> {
+    let mut paths = vec![PathBuf::from(puzzle.bin_path())];
+
+    // `DD.txt`, `DD-N.txt` and the manifest `DD.toml`.
+    if let Ok(entries) = fs::read_dir(data_dir(puzzle.year).join("examples")) {
+        let mut examples: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .filter(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .starts_with(&puzzle.day.to_string())
+            })
+            .map(|e| e.path())
+            .collect();
+        examples.sort();
+        paths.extend(examples);
+    }
+
+    match input {
+        InputSource::Puzzle => paths.push(puzzle.data_path("inputs")),
+        InputSource::Path(path) => paths.push(path.into()),
+        InputSource::Stdin | InputSource::Example(_) => {}
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let state = fs::metadata(&path)
+                .ok()
+                .and_then(|m| Some((m.modified().ok()?, m.len())));
+            (path, state)
+        })
+        .collect()
+}
+
+/// Run the tests of `puzzle` and print a single line with the outcome of each.
+fn print_test_summary(puzzle: Puzzle) {
+    let output = Command::new("cargo")
+        .args(["test", "--bin", &puzzle.bin_name()])
+        .stdin(Stdio::null())
+        .output();
+
+    let Ok(output) = output else {
+        println!("{ANSI_BOLD}Tests:{ANSI_RESET} could not run cargo test");
+        return;
+    };
+
+    let results = parse_test_results(&String::from_utf8_lossy(&output.stdout));
+
+    if results.is_empty() {
+        let reason = if output.status.success() {
+            "none found"
+        } else {
+            "failed to build"
+        };
+        println!("{ANSI_BOLD}Tests:{ANSI_RESET} {reason}");
+        return;
+    }
+
+    let passed = results.iter().filter(|(_, ok)| *ok).count();
+    let marks: Vec<String> = results
+        .iter()
+        .map(|(name, ok)| format!("{} {name}", if *ok { "✔" } else { "✘" }))
+        .collect();
+
+    println!(
+        "{ANSI_BOLD}Tests:{ANSI_RESET} {passed}/{} passed | {}",
+        results.len(),
+        marks.join(" | ")
+    );
+}
+
+/// Names of the tests in the output of the libtest harness, and whether they passed.
+/// Ignored tests are skipped, module paths are shortened to the name of the test.
+fn parse_test_results(stdout: &str) -> Vec<(String, bool)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, result) = line.strip_prefix("test ")?.split_once(" ... ")?;
+            let name = name.rsplit("::").next().unwrap_or(name).to_string();
+
+            match result.trim() {
+                "ok" => Some((name, true)),
+                "FAILED" => Some((name, false)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_test_results;
+
+    #[test]
+    fn parses_test_results() {
+        let stdout = "\nrunning 4 tests\ntest tests::test_part_one ... ok\ntest example_manifest::example_12_0 ... FAILED\ntest tests::test_part_two ... ignored\ntest tests::test_slow ... ignored, too slow\n\nfailures:\n\n---- example_manifest::example_12_0 stdout ----\ntest result: FAILED. 1 passed; 1 failed; 2 ignored\n";
+
+        assert_eq!(
+            parse_test_results(stdout),
+            vec![
+                ("test_part_one".into(), true),
+                ("example_12_0".into(), false)
+            ]
+        );
+    }
 }
@@ -1,34 +1,104 @@
 use std::collections::HashSet;
+use std::fs;
+use std::time::Duration;
 
+use crate::template::commands::CommandError;
+use crate::template::export::{self, Export};
+use crate::template::history::{self, History};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{compare, readme_benchmarks, Calendar, Puzzle};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Flags of `cargo time` that control which days are benched and what happens with the timings.
+#[derive(Clone, Debug, Default)]
+pub struct TimeOptions {
+    /// Bench all days, including the ones that already have stored timings.
+    pub run_all: bool,
+    /// Store the timings in `timings.json`, the history and the readme.
+    pub store: bool,
+    /// Compare the timings against the stored ones.
+    pub compare: bool,
+    /// Relative slowdown above which `compare` reports a regression.
+    pub threshold: Option<f64>,
+    /// Abort a day that runs longer than this.
+    pub timeout: Option<Duration>,
+    /// Write the timings to a file in another format.
+    pub export: Option<Export>,
+}
+
+pub fn handle(
+    puzzle: Option<Puzzle>,
+    year: Option<u16>,
+    history: Option<Puzzle>,
+    options: TimeOptions,
+) -> Result<(), CommandError> {
+    let TimeOptions {
+        run_all,
+        store,
+        compare,
+        threshold,
+        timeout,
+        export,
+    } = options;
+
+    if let Some(puzzle) = history {
+        history::print_history(&History::read_from_file(puzzle.year), puzzle);
+        return Ok(());
+    }
+
+    // a year-qualified day takes precedence over `--year`.
+    let year = puzzle.map_or(year, |p| p.year);
+    // NOTE: abort instead of starting empty, `--store` would otherwise overwrite the stored timings.
+    let stored_timings = Timings::read_from_file(year).map_err(CommandError::Failed)?;
+    let calendar = Calendar::resolve(year);
 
-    let days_to_run = day.map_or_else(
+    let days_to_run = puzzle.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                calendar.days().collect()
+            } else if compare {
+                // when comparing, only days with stored timings can be diffed.
+                calendar
+                    .days()
+                    .filter(|day| stored_timings.data.iter().any(|t| t.day == *day))
+                    .collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                calendar
+                    .days()
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |puzzle| HashSet::from([puzzle.day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // NOTE: always run sequentially so concurrent days do not disturb the benchmarks.
+    let is_release = true;
+    let summary = run_multi(year, &days_to_run, is_release, true, 1, false, timeout);
+    let timings = summary.timings.unwrap_or_default();
+
+    let has_regression = if compare {
+        println!();
+        let deltas = compare::compare(&stored_timings, &timings);
+        compare::print_table(&deltas, threshold.unwrap_or(compare::DEFAULT_THRESHOLD))
+    } else {
+        false
+    };
+
+    let merged_timings = stored_timings.merge(&timings);
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings
+            .store_file(year)
+            .map_err(CommandError::io("failed to store timings"))?;
+
+        if let Err(e) = History::append(&timings, year, is_release) {
+            eprintln!("Failed to append to timings history: {e}");
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings.clone()) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +107,31 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if let Some(Export { format, path }) = export {
+        if merged_timings.data.is_empty() {
+            return Err(CommandError::Failed(
+                "no timings to export, run or store some days first.".into(),
+            ));
+        }
+
+        // days that were not run this time are exported with their stored timings.
+        let contents = export::export(&merged_timings, year, format);
+        fs::write(&path, contents).map_err(CommandError::io(format!(
+            "failed to export timings to \"{}\"",
+            path.display()
+        )))?;
+        println!("Exported timings to \"{}\".", path.display());
+    }
+
+    // days that did not succeed are still stored and exported, with the timings of the parts that did.
+    if summary.timed_out > 0 || summary.failed > 0 || summary.panicked > 0 {
+        return Err(CommandError::SolutionFailed);
+    }
+
+    if has_regression {
+        return Err(CommandError::Regression);
+    }
+
+    Ok(())
 }
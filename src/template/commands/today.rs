@@ -0,0 +1,82 @@
+use crate::template::commands::{download, read, scaffold, CommandError};
+use crate::template::{aoc_cli, Calendar, Clock, Puzzle, SystemClock};
+
+pub fn handle() -> Result<(), CommandError> {
+    let puzzle = todays_puzzle(&SystemClock, aoc_cli::get_year()).map_err(CommandError::Failed)?;
+
+    scaffold::handle(puzzle, false)?;
+    download::handle(puzzle)?;
+    read::handle(puzzle)
+}
+
+/// The puzzle that unlocked today. Days of the event in `AOC_YEAR` use the flat layout, others are year-qualified.
+fn todays_puzzle(clock: &impl Clock, flat_year: Option<u16>) -> Result<Puzzle, String> {
+    let calendar = Calendar::latest(clock);
+
+    if let Some(day) = calendar.today(clock) {
+        let year = Some(calendar.year()).filter(|year| flat_year.is_some_and(|y| y != *year));
+        return Ok(Puzzle::new(year, day));
+    }
+
+    let mut message = "`today` command can only be run while an event is running.".to_string();
+
+    let next = [calendar, Calendar::new(calendar.year() + 1)]
+        .into_iter()
+        .find_map(|c| {
+            let (day, unlock_time) = c.next_unlock(clock)?;
+            Some((Puzzle::new(Some(c.year()), day), unlock_time))
+        });
+
+    if let Some((puzzle, unlock_time)) = next {
+        let wait = unlock_time - clock.now();
+        message.push_str(&format!(
+            " Day {puzzle} unlocks in {}d {}h {}m.",
+            wait / 86_400,
+            wait % 86_400 / 3600,
+            wait % 3600 / 60
+        ));
+    }
+
+    message.push_str(" Please use `scaffold` with a specific day.");
+    Err(message)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::todays_puzzle;
+    use crate::day;
+    use crate::template::calendar::DEC_1_2024;
+    use crate::template::{FixedClock, Puzzle};
+
+    #[test]
+    fn uses_flat_layout_for_current_year() {
+        let clock = FixedClock(DEC_1_2024 + 86_400 + 60);
+        assert_eq!(
+            todays_puzzle(&clock, Some(2024)),
+            Ok(Puzzle::new(None, day!(2)))
+        );
+        assert_eq!(todays_puzzle(&clock, None), Ok(Puzzle::new(None, day!(2))));
+    }
+
+    #[test]
+    fn qualifies_other_years() {
+        let clock = FixedClock(DEC_1_2024);
+        assert_eq!(
+            todays_puzzle(&clock, Some(2023)),
+            Ok(Puzzle::new(Some(2024), day!(1)))
+        );
+    }
+
+    #[test]
+    fn reports_next_unlock_outside_of_events() {
+        let message = todays_puzzle(&FixedClock(DEC_1_2024 - 3600), Some(2024)).unwrap_err();
+        assert!(message.contains("Day 2024/01 unlocks in 0d 1h 0m."));
+
+        // the 2025 event only has 12 days.
+        let dec_13_2025 = DEC_1_2024 + 377 * 86_400;
+        let message = todays_puzzle(&FixedClock(dec_13_2025), Some(2025)).unwrap_err();
+        assert!(message.contains("Day 2026/01 unlocks in"));
+    }
+}
@@ -0,0 +1,145 @@
+/// Module that compares fresh benchmark results against stored timings.
+use crate::template::stats::to_duration;
+use crate::template::timings::Timings;
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Regression threshold in percent that is used when none is passed via `--threshold`.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Change of a single part between the stored and the current benchmark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    pub stored_nanos: f64,
+    pub current_nanos: f64,
+}
+
+impl PartDelta {
+    /// Relative change in percent. Positive values mean the part got slower.
+    pub fn change_percent(&self) -> f64 {
+        if self.stored_nanos == 0.0 {
+            return 0.0;
+        }
+        (self.current_nanos - self.stored_nanos) / self.stored_nanos * 100.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold
+    }
+}
+
+/// Pairs up every part that is timed in both `stored` and `current`.
+pub fn compare(stored: &Timings, current: &Timings) -> Vec<PartDelta> {
+    let mut deltas = vec![];
+
+    for timing in &current.data {
+        let Some(stored_timing) = stored.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for part in [1, 2] {
+            if let (Some(stored_nanos), Some(current_nanos)) =
+                (stored_timing.part_nanos(part), timing.part_nanos(part))
+            {
+                deltas.push(PartDelta {
+                    day: timing.day,
+                    part,
+                    stored_nanos,
+                    current_nanos,
+                });
+            }
+        }
+    }
+
+    deltas
+}
+
+/// Prints a per-part delta table and returns `true` if any part regressed beyond `threshold`.
+pub fn print_table(deltas: &[PartDelta], threshold: f64) -> bool {
+    println!("{ANSI_BOLD}Comparison (threshold: {threshold:.1}%){ANSI_RESET}");
+    println!("------");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare against.");
+        return false;
+    }
+
+    println!("Day | Part | Stored | Current | Change");
+
+    let mut has_regression = false;
+
+    for delta in deltas {
+        let marker = if delta.is_regression(threshold) {
+            has_regression = true;
+            " ✘"
+        } else {
+            ""
+        };
+
+        println!(
+            "{} | {} | {:.1?} | {:.1?} | {:+.1}%{marker}",
+            delta.day,
+            delta.part,
+            to_duration(delta.stored_nanos),
+            to_duration(delta.current_nanos),
+            delta.change_percent()
+        );
+    }
+
+    has_regression
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, PartDelta};
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    fn timing(day: crate::template::Day, part_1: Option<f64>, part_2: Option<f64>) -> Timing {
+        let mut timing = Timing::new(day);
+        timing.part_1 = part_1.map(|nanos| PartTiming::solved(nanos, 1));
+        timing.part_2 = part_2.map(|nanos| PartTiming::solved(nanos, 1));
+        timing
+    }
+
+    #[test]
+    fn compares_overlapping_parts() {
+        let stored = Timings {
+            data: vec![
+                timing(day!(1), Some(10e6), Some(20e6)),
+                timing(day!(2), Some(1e6), None),
+            ],
+        };
+        let current = Timings {
+            data: vec![
+                timing(day!(1), Some(12e6), Some(10e6)),
+                timing(day!(2), Some(1e6), Some(5e6)),
+                timing(day!(3), Some(1e6), Some(1e6)),
+            ],
+        };
+
+        let deltas = compare(&stored, &current);
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].part, 1);
+        assert!((deltas[0].change_percent() - 20.0).abs() < 1e-9);
+        assert!((deltas[1].change_percent() + 50.0).abs() < 1e-9);
+        assert_eq!(deltas[2].day, day!(2));
+        assert_eq!(deltas[2].change_percent(), 0.0);
+    }
+
+    #[test]
+    fn detects_regressions_beyond_threshold() {
+        let delta = PartDelta {
+            day: day!(1),
+            part: 1,
+            stored_nanos: 100.0,
+            current_nanos: 115.0,
+        };
+        assert!(delta.is_regression(10.0));
+        assert!(!delta.is_regression(20.0));
+    }
+}
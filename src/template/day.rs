@@ -2,13 +2,11 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
-#[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
-
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+use crate::template::calendar::MAX_DAYS;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// The range is the same for every year: events with fewer days (12 since 2025) are not taken into
+/// account here. Whether a day is part of a specific event is answered by its [`Calendar`](crate::template::Calendar).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -23,9 +21,10 @@ pub struct Day(u8);
 
 impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
-    /// returns [`None`] otherwise.
+    /// returns [`None`] otherwise. Use [`Calendar::contains`](crate::template::Calendar::contains)
+    /// to check that the day exists in a given event.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > MAX_DAYS {
             return None;
         }
         Some(Self(day))
@@ -38,25 +37,11 @@ impl Day {
     }
 
     /// Converts the [`Day`] into an [`u8`].
-    pub fn into_inner(self) -> u8 {
+    pub const fn into_inner(self) -> u8 {
         self.0
     }
 }
 
-#[cfg(feature = "today")]
-impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
-    pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
-    }
-}
-
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:02}", self.0)
@@ -100,20 +85,30 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the 25th, regardless of the year.
+/// Use [`Calendar::days`](crate::template::Calendar::days) for the days of a specific event.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(MAX_DAYS)
+    }
+
+    /// Yields the days from the 1st to `last`, which is capped at the 25th.
+    pub fn until(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(MAX_DAYS),
+        }
     }
 }
 
@@ -121,10 +116,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,11 +130,12 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// Like [`Day::new`], this only checks the range 1 to 25, not the length of a specific event.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::calendar::MAX_DAYS,
             concat!(
                 "invalid day number `",
                 $day,
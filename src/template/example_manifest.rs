@@ -0,0 +1,407 @@
+/// Parser and writer for the per-day example manifests in `data/examples/DD.toml`.
+///
+/// A manifest lists the examples of a day with their expected answers, in a small subset of TOML:
+///
+/// ```toml
+/// [[example]]
+/// file = "12-0.txt"
+/// part_1 = 140
+/// part_2 = "80"
+/// params = { width = 11, height = 7 }
+/// ```
+///
+/// This module only depends on `std`, as `build.rs` includes it to generate one test per example.
+use std::collections::HashMap;
+
+/// A single example of a manifest.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    /// File name of the example, relative to the examples folder of the day.
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Solution parameters, e.g. a grid size that differs between the example and the puzzle input.
+    pub params: Vec<(String, String)>,
+}
+
+impl Example {
+    /// The expected answer of `part`, if any.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Parse the contents of a manifest. Errors are prefixed with the line they occurred on.
+pub fn parse(s: &str) -> Result<Vec<Example>, String> {
+    let mut examples: Vec<Example> = vec![];
+    let mut keys: HashMap<String, usize> = HashMap::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line_number = i + 1;
+        let err = |e: String| format!("line {line_number}: {e}");
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            if line != "[[example]]" {
+                return Err(err(format!(
+                    "unsupported table {line}, expected [[example]]."
+                )));
+            }
+            examples.push(Example::default());
+            keys.clear();
+            continue;
+        }
+
+        let example = examples
+            .last_mut()
+            .ok_or_else(|| err("expected [[example]] before the first key.".into()))?;
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected a key = value pair.".into()))?;
+        let key = key.trim();
+
+        if let Some(first) = keys.insert(key.to_string(), line_number) {
+            return Err(err(format!(
+                "duplicate key \"{key}\", first defined on line {first}."
+            )));
+        }
+
+        let mut value = Value::new(value.trim());
+
+        match key {
+            "file" => example.file = value.string().map_err(err)?,
+            "part_1" => example.part_1 = Some(value.scalar().map_err(err)?),
+            "part_2" => example.part_2 = Some(value.scalar().map_err(err)?),
+            "params" => example.params = value.table().map_err(err)?,
+            _ => return Err(err(format!("unknown key \"{key}\"."))),
+        }
+
+        value.end().map_err(err)?;
+    }
+
+    for (i, example) in examples.iter().enumerate() {
+        if example.file.is_empty() {
+            return Err(format!("example {} is missing a file.", i + 1));
+        }
+    }
+
+    Ok(examples)
+}
+
+/// Format `example` as a `[[example]]` entry that [`parse`] reads back, e.g. to append it to a manifest.
+pub fn format(example: &Example) -> String {
+    let mut lines = vec![
+        "[[example]]".to_string(),
+        format!("file = {}", quote(&example.file)),
+    ];
+
+    for (key, answer) in [("part_1", &example.part_1), ("part_2", &example.part_2)] {
+        if let Some(answer) = answer {
+            lines.push(format!("{key} = {}", scalar(answer)));
+        }
+    }
+
+    if !example.params.is_empty() {
+        let params: Vec<String> = example
+            .params
+            .iter()
+            .map(|(key, value)| format!("{key} = {}", scalar(value)))
+            .collect();
+        lines.push(format!("params = {{ {} }}", params.join(", ")));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// A value as written in a manifest: numbers and booleans are left bare, anything else is quoted.
+fn scalar(value: &str) -> String {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let is_number = !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit());
+
+    if is_number || value == "true" || value == "false" {
+        value.to_string()
+    } else {
+        quote(value)
+    }
+}
+
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{escaped}\"")
+}
+
+/// The part of `line` before a `#` that is not inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut is_escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if is_escaped => is_escaped = false,
+            '\\' if in_string => is_escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+/// Cursor over the value of a key.
+struct Value<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Value<'a> {
+    fn new(s: &'a str) -> Self {
+        Self { rest: s }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn end(&mut self) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("unexpected \"{}\" after value.", self.rest))
+        }
+    }
+
+    /// A string, number or boolean, as written in the manifest.
+    fn scalar(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+
+        if self.rest.starts_with('"') {
+            return self.string();
+        }
+
+        let end = self
+            .rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '_' | '.')))
+            .unwrap_or(self.rest.len());
+        let (token, rest) = self.rest.split_at(end);
+
+        let is_number = token
+            .trim_start_matches(['-', '+'])
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '_' || c == '.')
+            && token.chars().any(|c| c.is_ascii_digit());
+
+        if token == "true" || token == "false" {
+            self.rest = rest;
+            Ok(token.to_string())
+        } else if is_number {
+            self.rest = rest;
+            Ok(token.trim_start_matches('+').replace('_', ""))
+        } else {
+            Err("expected a string, number or boolean.".into())
+        }
+    }
+
+    /// A basic string in double quotes. Supports the escapes `\"`, `\\`, `\n` and `\t`.
+    fn string(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+
+        let mut chars = self
+            .rest
+            .strip_prefix('"')
+            .ok_or("expected a string in double quotes.")?
+            .char_indices();
+        let mut s = String::new();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.rest = &self.rest[i + 2..];
+                    return Ok(s);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('"') => s.push('"'),
+                    Some('\\') => s.push('\\'),
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    _ => return Err("unsupported escape sequence in string.".into()),
+                },
+                _ => s.push(c),
+            }
+        }
+
+        Err("unterminated string.".into())
+    }
+
+    /// An inline table of scalars, e.g. `{ width = 11, height = 7 }`.
+    fn table(&mut self) -> Result<Vec<(String, String)>, String> {
+        self.skip_whitespace();
+        self.rest = self
+            .rest
+            .strip_prefix('{')
+            .ok_or("expected an inline table.")?;
+
+        let mut entries: Vec<(String, String)> = vec![];
+
+        loop {
+            self.skip_whitespace();
+
+            if let Some(rest) = self.rest.strip_prefix('}') {
+                self.rest = rest;
+                return Ok(entries);
+            }
+
+            if !entries.is_empty() {
+                self.rest = self
+                    .rest
+                    .strip_prefix(',')
+                    .ok_or("expected , or } in inline table.")?;
+                self.skip_whitespace();
+            }
+
+            let (key, rest) = self
+                .rest
+                .split_once('=')
+                .ok_or("expected a key = value pair in inline table.")?;
+            let key = key.trim();
+
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("invalid key \"{key}\" in inline table."));
+            }
+
+            self.rest = rest;
+            entries.push((key.to_string(), self.scalar()?));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format, parse, Example};
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = r#"
+            # examples of day 12
+            [[example]]
+            file = "12.txt"
+            part_1 = 1_930 # inline comment
+            part_2 = "1206"
+
+            [[example]]
+            file = "12-0.txt"
+            part_2 = "a # b\n\"c\""
+            params = { width = 11, height = -7, wrap = true, name = "x, y" }
+        "#;
+
+        assert_eq!(
+            parse(manifest).unwrap(),
+            vec![
+                Example {
+                    file: "12.txt".into(),
+                    part_1: Some("1930".into()),
+                    part_2: Some("1206".into()),
+                    params: vec![],
+                },
+                Example {
+                    file: "12-0.txt".into(),
+                    part_1: None,
+                    part_2: Some("a # b\n\"c\"".into()),
+                    params: vec![
+                        ("width".into(), "11".into()),
+                        ("height".into(), "-7".into()),
+                        ("wrap".into(), "true".into()),
+                        ("name".into(), "x, y".into()),
+                    ],
+                },
+            ]
+        );
+        assert_eq!(parse("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn formats_examples() {
+        let examples = vec![
+            Example {
+                file: "12-1.txt".into(),
+                part_1: Some("140".into()),
+                part_2: Some("a \"b\"\\c\nd".into()),
+                params: vec![],
+            },
+            Example {
+                file: "12-2.txt".into(),
+                part_1: None,
+                part_2: Some("-7".into()),
+                params: vec![
+                    ("width".into(), "11".into()),
+                    ("name".into(), "x, y".into()),
+                ],
+            },
+        ];
+
+        assert_eq!(
+            format(&examples[0]),
+            "[[example]]\nfile = \"12-1.txt\"\npart_1 = 140\npart_2 = \"a \\\"b\\\"\\\\c\\nd\"\n"
+        );
+
+        let manifest: String = examples.iter().map(format).collect::<Vec<_>>().join("\n");
+        assert_eq!(parse(&manifest).unwrap(), examples);
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        let error = |s: &str| parse(s).unwrap_err();
+
+        assert_eq!(
+            error("file = \"01.txt\""),
+            "line 1: expected [[example]] before the first key."
+        );
+        assert_eq!(
+            error("[example]"),
+            "line 1: unsupported table [example], expected [[example]]."
+        );
+        assert_eq!(
+            error("[[example]]\nfile = \"01.txt\"\nfile = \"02.txt\""),
+            "line 3: duplicate key \"file\", first defined on line 2."
+        );
+        assert_eq!(
+            error("[[example]]\nfile = \"01.txt\"\npart_3 = 1"),
+            "line 3: unknown key \"part_3\"."
+        );
+        assert_eq!(
+            error("[[example]]\nfile = \"01.txt\nAnswer"),
+            "line 2: unterminated string."
+        );
+        assert_eq!(
+            error("[[example]]\nfile = \"01.txt\" 1"),
+            "line 2: unexpected \"1\" after value."
+        );
+        assert_eq!(
+            error("[[example]]\nfile = \"01.txt\"\npart_1 = abc"),
+            "line 3: expected a string, number or boolean."
+        );
+        assert_eq!(
+            error("[[example]]\nfile = \"01.txt\"\nparams = { a = 1 b = 2 }"),
+            "line 3: expected , or } in inline table."
+        );
+        assert_eq!(
+            error("[[example]]\npart_1 = 1"),
+            "example 1 is missing a file."
+        );
+    }
+}
@@ -0,0 +1,130 @@
+/// Runs the examples of a day's manifest in `data/examples/DD.toml` as tests.
+///
+/// `build.rs` turns every entry of a manifest into a `#[test]` that calls [`check`].
+/// `solution!` includes the generated tests, so adding an example does not need new test code.
+use std::cell::RefCell;
+use std::fs;
+use std::str::FromStr;
+
+use crate::template::data_dir;
+use crate::template::runner::{PartStatus, RunOptions, Solution, PARSE_PART};
+
+pub use crate::template::example_manifest::{parse, Example};
+
+thread_local! {
+    static PARAMS: RefCell<Vec<(String, String)>> = const { RefCell::new(vec![]) };
+}
+
+/// Value of the solution parameter `name` of the example that is being checked.
+/// Returns `None` outside of example tests, so solutions can fall back to the values of the puzzle input.
+///
+/// ```ignore
+/// let size = advent_of_code::template::examples::param("size").unwrap_or(71);
+/// ```
+pub fn param<T: FromStr>(name: &str) -> Option<T> {
+    PARAMS.with(|params| {
+        params
+            .borrow()
+            .iter()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.parse().ok())
+    })
+}
+
+/// Run `solution` on `example` and panic if an expected answer does not match.
+pub fn check(solution: &Solution, example: &Example) {
+    let path = data_dir(solution.puzzle.year)
+        .join("examples")
+        .join(&example.file);
+
+    let input = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("could not read example \"{}\": {e}", path.display()));
+
+    PARAMS.with(|params| *params.borrow_mut() = example.params.clone());
+    let results = (solution.run)(&input, &RunOptions::default());
+    PARAMS.with(|params| params.borrow_mut().clear());
+
+    let mut failures: Vec<String> = vec![];
+
+    if results
+        .iter()
+        .any(|r| r.part == PARSE_PART && r.status == PartStatus::Panicked)
+    {
+        failures.push("parse step panicked".into());
+    }
+
+    for part in [1, 2] {
+        let Some(expected) = example.expected(part) else {
+            continue;
+        };
+
+        let actual = match results.iter().find(|r| r.part == part) {
+            Some(r) if r.status == PartStatus::Panicked => "a panic".into(),
+            Some(r) if r.status == PartStatus::Failed => "an error".into(),
+            Some(r) => match r.answer.as_deref() {
+                Some(answer) if answer.trim() == expected.trim() => continue,
+                Some(answer) => format!("{answer:?}"),
+                None => "no answer".into(),
+            },
+            None if failures.is_empty() => "a solution without this part".into(),
+            None => continue,
+        };
+
+        failures.push(format!("part {part}: expected {expected:?}, got {actual}"));
+    }
+
+    assert!(
+        failures.is_empty(),
+        "example {} failed: {}",
+        example.file,
+        failures.join(", ")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, param, Example};
+    use crate::day;
+    use crate::template::runner::{run_part_with_options, Solution};
+    use crate::template::Puzzle;
+
+    fn part_one(_input: &str) -> Option<u32> {
+        param("answer")
+    }
+
+    const SOLUTION: Solution = Solution {
+        puzzle: Puzzle::new(None, day!(1)),
+        run: |input, options| {
+            vec![run_part_with_options(
+                part_one,
+                input,
+                SOLUTION.puzzle,
+                1,
+                options,
+            )]
+        },
+    };
+
+    fn example(answer: &str) -> Example {
+        Example {
+            file: "01.txt".into(),
+            part_1: Some("42".into()),
+            part_2: None,
+            params: vec![("answer".into(), answer.into())],
+        }
+    }
+
+    #[test]
+    fn checks_examples_with_params() {
+        check(&SOLUTION, &example("42"));
+        assert_eq!(param::<u32>("answer"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "example 01.txt failed: part 1: expected \"42\", got \"41\"")]
+    fn fails_on_wrong_answers() {
+        check(&SOLUTION, &example("41"));
+    }
+}
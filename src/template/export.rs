@@ -0,0 +1,356 @@
+/// Exports timings to formats that spreadsheets and test-report viewers understand.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::alloc::MemoryStats;
+use crate::template::stats::{to_duration, BenchStats};
+use crate::template::timings::{Timing, TimingStatus, Timings};
+use crate::template::Puzzle;
+
+/// A file format timings can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One row per day and part.
+    Csv,
+    /// One JSON object per day and part.
+    JsonLines,
+    /// A JUnit XML report with one test suite per day and one test case per part.
+    Junit,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "jsonl" => Ok(Self::JsonLines),
+            "junit" => Ok(Self::Junit),
+            _ => Err(format!(
+                "expecting an export format of csv, jsonl or junit, got \"{s}\""
+            )),
+        }
+    }
+}
+
+/// Where `cargo time --export <format> --output <path>` writes its timings to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Export {
+    pub format: ExportFormat,
+    pub path: PathBuf,
+}
+
+/// The timing of one part of a day. Part `0` is the shared parse step.
+struct Row<'a> {
+    puzzle: Puzzle,
+    part: u8,
+    timing: &'a Timing,
+}
+
+impl Row<'_> {
+    fn name(&self) -> String {
+        match self.part {
+            0 => "parse".into(),
+            part => format!("part_{part}"),
+        }
+    }
+
+    fn nanos(&self) -> Option<f64> {
+        self.timing.part_nanos(self.part)
+    }
+
+    fn samples(&self) -> Option<u128> {
+        self.timing
+            .part(self.part)
+            .filter(|p| p.is_solved())
+            .map(|p| p.samples)
+    }
+
+    fn stats(&self) -> Option<BenchStats> {
+        self.timing.part(self.part).and_then(|p| p.stats)
+    }
+
+    fn memory(&self) -> Option<MemoryStats> {
+        self.timing.part(self.part).and_then(|p| p.memory)
+    }
+
+    /// The status of the part, or `None` if no timing was recorded for it.
+    fn status(&self) -> Option<TimingStatus> {
+        self.timing.part(self.part).map(|p| p.status)
+    }
+
+    /// The status as it is written to the export: `solved`, `timed_out` or `missing`.
+    fn status_name(&self) -> &'static str {
+        self.status().map_or("missing", TimingStatus::as_str)
+    }
+}
+
+/// One row per part of every day. The parse step is only included for days that have one.
+fn rows(timings: &Timings, year: Option<u16>) -> Vec<Row<'_>> {
+    timings
+        .data
+        .iter()
+        .flat_map(|timing| {
+            let parts: &[u8] = if timing.parse.is_some() {
+                &[0, 1, 2]
+            } else {
+                &[1, 2]
+            };
+
+            parts.iter().map(move |part| Row {
+                puzzle: Puzzle::new(year, timing.day),
+                part: *part,
+                timing,
+            })
+        })
+        .collect()
+}
+
+/// Render `timings` of `year` in `format`.
+pub fn export(timings: &Timings, year: Option<u16>, format: ExportFormat) -> String {
+    let rows = rows(timings, year);
+
+    match format {
+        ExportFormat::Csv => to_csv(&rows),
+        ExportFormat::JsonLines => to_json_lines(&rows),
+        ExportFormat::Junit => to_junit(&rows, year),
+    }
+}
+
+static CSV_HEADER: &str = "day,part,status,median_nanos,min_nanos,p95_nanos,std_dev_nanos,samples,peak_bytes,allocated_bytes,allocations";
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_csv(rows: &[Row]) -> String {
+    fn cell<T: Display>(value: Option<T>) -> String {
+        value.map(|v| v.to_string()).unwrap_or_default()
+    }
+
+    let mut lines = vec![CSV_HEADER.to_string()];
+
+    for row in rows {
+        let stats = row.stats();
+        let memory = row.memory();
+
+        lines.push(
+            [
+                row.puzzle.to_string(),
+                row.name(),
+                row.status_name().into(),
+                cell(row.nanos().map(|n| n.round() as u64)),
+                cell(stats.map(|s| s.min.round() as u64)),
+                cell(stats.map(|s| s.p95.round() as u64)),
+                cell(stats.map(|s| s.std_dev.round() as u64)),
+                cell(row.samples()),
+                cell(memory.map(|m| m.peak_bytes)),
+                cell(memory.map(|m| m.total_bytes)),
+                cell(memory.map(|m| m.allocations)),
+            ]
+            .join(","),
+        );
+    }
+
+    lines.join("\n") + "\n"
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn to_json_lines(rows: &[Row]) -> String {
+    let number = |value: Option<f64>| value.map_or(JsonValue::Null, JsonValue::Number);
+
+    rows.iter()
+        .map(|row| {
+            let stats = row.stats();
+            let memory = row.memory();
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+            map.insert("day".into(), JsonValue::String(row.puzzle.to_string()));
+            map.insert("part".into(), JsonValue::String(row.name()));
+            map.insert("status".into(), JsonValue::String(row.status_name().into()));
+            map.insert("median_nanos".into(), number(row.nanos()));
+            map.insert("min_nanos".into(), number(stats.map(|s| s.min)));
+            map.insert("p95_nanos".into(), number(stats.map(|s| s.p95)));
+            map.insert("std_dev_nanos".into(), number(stats.map(|s| s.std_dev)));
+            map.insert("samples".into(), number(row.samples().map(|s| s as f64)));
+            map.insert(
+                "memory".into(),
+                memory.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+
+            // a map of numbers, strings and nulls always serializes.
+            JsonValue::Object(map).stringify().unwrap_or_default() + "\n"
+        })
+        .collect()
+}
+
+fn to_junit(rows: &[Row], year: Option<u16>) -> String {
+    let name = match year {
+        Some(year) => format!("Advent of Code {year} benchmarks"),
+        None => "Advent of Code benchmarks".into(),
+    };
+
+    let seconds = |rows: &[&Row]| -> f64 {
+        rows.iter()
+            .filter_map(|r| r.nanos())
+            .map(|n| to_duration(n).as_secs_f64())
+            .sum()
+    };
+
+    let all: Vec<&Row> = rows.iter().collect();
+    let failures = |rows: &[&Row]| {
+        rows.iter()
+            .filter(|r| r.status() == Some(TimingStatus::TimedOut))
+            .count()
+    };
+    let skipped = |rows: &[&Row]| rows.iter().filter(|r| r.status().is_none()).count();
+
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+        format!(
+            r#"<testsuites name="{}" tests="{}" failures="{}" skipped="{}" time="{:.9}">"#,
+            escape_xml(&name),
+            all.len(),
+            failures(&all),
+            skipped(&all),
+            seconds(&all)
+        ),
+    ];
+
+    let mut days: Vec<Vec<&Row>> = vec![];
+    for row in rows {
+        match days.last_mut() {
+            Some(day) if day[0].puzzle == row.puzzle => day.push(row),
+            _ => days.push(vec![row]),
+        }
+    }
+
+    for day in days {
+        let puzzle = day[0].puzzle;
+
+        lines.push(format!(
+            r#"  <testsuite name="Day {}" tests="{}" failures="{}" skipped="{}" time="{:.9}">"#,
+            escape_xml(&puzzle.to_string()),
+            day.len(),
+            failures(&day),
+            skipped(&day),
+            seconds(&day)
+        ));
+
+        for row in day {
+            let case = format!(
+                r#"    <testcase classname="{}" name="{}" time="{:.9}""#,
+                escape_xml(&format!("day_{}", puzzle.bin_name())),
+                row.name(),
+                row.nanos().map_or(0.0, |n| to_duration(n).as_secs_f64())
+            );
+
+            lines.push(match row.status() {
+                Some(TimingStatus::Solved) => format!("{case}/>"),
+                Some(TimingStatus::TimedOut) => {
+                    format!("{case}>\n      <failure message=\"timed out\"/>\n    </testcase>")
+                }
+                None => format!(
+                    "{case}>\n      <skipped message=\"no timing recorded\"/>\n    </testcase>"
+                ),
+            });
+        }
+
+        lines.push("  </testsuite>".into());
+    }
+
+    lines.push("</testsuites>".into());
+    lines.join("\n") + "\n"
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{export, ExportFormat};
+    use crate::day;
+    use crate::template::stats::BenchStats;
+    use crate::template::timings::{PartTiming, Timing, Timings};
+
+    fn get_mock_timings() -> Timings {
+        let mut first = Timing::new(day!(1));
+        first.part_1 = Some(PartTiming::solved(10_000_000.0, 10));
+        first.part_2 = Some(PartTiming::solved(20_000.0, 1));
+        first.part_1.as_mut().unwrap().stats = Some(BenchStats {
+            samples: 10,
+            mean: 10_000_000.0,
+            min: 9_000_000.0,
+            median: 10_000_000.0,
+            p95: 12_000_000.0,
+            std_dev: 500_000.0,
+            outliers: 0,
+        });
+
+        let mut second = Timing::new(day!(2));
+        second.part_1 = Some(PartTiming::solved(1_500_000_000.0, 1));
+        second.part_2 = Some(PartTiming::timed_out());
+
+        Timings {
+            data: vec![first, second],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("csv".parse(), Ok(ExportFormat::Csv));
+        assert_eq!("jsonl".parse(), Ok(ExportFormat::JsonLines));
+        assert_eq!("junit".parse(), Ok(ExportFormat::Junit));
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = export(&get_mock_timings(), None, ExportFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[1],
+            "01,part_1,solved,10000000,9000000,12000000,500000,10,,,"
+        );
+        assert_eq!(lines[2], "01,part_2,solved,20000,,,,1,,,");
+        assert_eq!(lines[4], "02,part_2,timed_out,,,,,,,,");
+    }
+
+    #[test]
+    fn exports_json_lines() {
+        let jsonl = export(&get_mock_timings(), Some(2023), ExportFormat::JsonLines);
+        let lines: Vec<&str> = jsonl.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].contains(r#""day":"2023/01""#));
+        assert!(lines[0].contains(r#""median_nanos":10000000"#));
+        assert!(lines[3].contains(r#""status":"timed_out""#));
+        assert!(lines[3].contains(r#""median_nanos":null"#));
+    }
+
+    #[test]
+    fn exports_junit() {
+        let xml = export(&get_mock_timings(), None, ExportFormat::Junit);
+
+        assert!(xml.contains(
+            r#"<testsuites name="Advent of Code benchmarks" tests="4" failures="1" skipped="0" time="1.510020000">"#
+        ));
+        assert!(xml.contains(
+            r#"<testsuite name="Day 01" tests="2" failures="0" skipped="0" time="0.010020000">"#
+        ));
+        assert!(xml.contains(r#"<testcase classname="day_01" name="part_1" time="0.010000000"/>"#));
+        assert!(xml.contains(
+            "<testcase classname=\"day_02\" name=\"part_2\" time=\"0.000000000\">\n      <failure message=\"timed out\"/>"
+        ));
+        assert!(xml.ends_with("</testsuites>\n"));
+    }
+}
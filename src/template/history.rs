@@ -0,0 +1,306 @@
+/// Module that keeps an append-only log of every stored benchmark run.
+/// Each line of the log file is a JSON object describing the timing of one day.
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::calendar::civil_from_timestamp;
+use crate::template::stats::to_duration;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{data_dir, Day, Puzzle, ANSI_BOLD, ANSI_RESET};
+
+static HISTORY_FILE_NAME: &str = "timings_history.jsonl";
+
+/// A timing of a single day, tagged with the context it was recorded in.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub commit: Option<String>,
+    pub profile: String,
+    pub timing: Timing,
+}
+
+/// All recorded history entries, in the order they were appended.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Append one entry per day in `timings` to the history file, tagged with the build profile
+    /// the timings were recorded with.
+    pub fn append(timings: &Timings, year: Option<u16>, is_release: bool) -> Result<(), io::Error> {
+        let profile = if is_release { "release" } else { "dev" };
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let commit = current_commit();
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(data_dir(year).join(HISTORY_FILE_NAME))?;
+
+        for timing in &timings.data {
+            let entry = HistoryEntry {
+                timestamp,
+                commit: commit.clone(),
+                profile: profile.into(),
+                timing: timing.clone(),
+            };
+            let line = JsonValue::from(&entry)
+                .stringify()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            writeln!(file, "{line}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Read the history from its file. If not present, returns an empty history.
+    /// Lines that cannot be parsed are skipped.
+    pub fn read_from_file(year: Option<u16>) -> Self {
+        fs::read_to_string(data_dir(year).join(HISTORY_FILE_NAME))
+            .map(|s| History::from_lines(&s))
+            .unwrap_or_default()
+    }
+
+    fn from_lines(s: &str) -> Self {
+        let entries = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| {
+                let entry = JsonValue::from_str(l)
+                    .map_err(|_| "not valid JSON.".to_string())
+                    .and_then(|json| HistoryEntry::try_from(&json));
+
+                match entry {
+                    Ok(entry) => Some(entry),
+                    Err(e) => {
+                        eprintln!("Skipping malformed history entry: {e}");
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        History { entries }
+    }
+
+    pub fn for_day(&self, day: Day) -> Vec<&HistoryEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.timing.day == day)
+            .collect()
+    }
+}
+
+/// Print how the runtime of each part of `puzzle` evolved across recorded runs.
+pub fn print_history(history: &History, puzzle: Puzzle) {
+    println!("{ANSI_BOLD}Day {puzzle} history{ANSI_RESET}");
+    println!("------");
+
+    let entries = history.for_day(puzzle.day);
+
+    if entries.is_empty() {
+        println!("No history recorded. Run `cargo time {puzzle} --store` to record one.");
+        return;
+    }
+
+    println!("Date | Commit | Profile | Part 1 | Part 2");
+
+    let mut previous: Option<&HistoryEntry> = None;
+
+    for entry in entries {
+        let part_columns: Vec<String> = [1, 2]
+            .iter()
+            .map(|&part| {
+                let Some(nanos) = entry.timing.part_nanos(part) else {
+                    return "-".into();
+                };
+
+                let change = previous
+                    .and_then(|p| p.timing.part_nanos(part))
+                    .filter(|prev| *prev > 0.0)
+                    .map(|prev| format!(" ({:+.1}%)", (nanos - prev) / prev * 100.0))
+                    .unwrap_or_default();
+
+                format!("{:.1?}{change}", to_duration(nanos))
+            })
+            .collect();
+
+        println!(
+            "{} | {} | {} | {} | {}",
+            format_timestamp(entry.timestamp),
+            entry.commit.as_deref().unwrap_or("-"),
+            entry.profile,
+            part_columns[0],
+            part_columns[1]
+        );
+
+        previous = Some(entry);
+    }
+}
+
+/// Short hash of the checked out git commit, suffixed with `-dirty` if the working tree has changes.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD HH:MM` (UTC).
+#[allow(clippy::cast_possible_wrap)]
+fn format_timestamp(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_timestamp(timestamp as i64);
+    let secs_of_day = timestamp % 86_400;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.profile to be a string.")?;
+
+        let timing = Timing::try_from(
+            json.get("timing")
+                .ok_or("Expected entry to have key `timing`.")?,
+        )?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit: commit.cloned(),
+            profile: profile.clone(),
+            timing,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, History, HistoryEntry};
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing},
+    };
+    use tinyjson::JsonValue;
+
+    fn entry(timestamp: u64, part_1_nanos: f64) -> HistoryEntry {
+        let mut timing = Timing::new(day!(6));
+        timing.part_1 = Some(PartTiming::solved(part_1_nanos, 10));
+
+        HistoryEntry {
+            timestamp,
+            commit: Some("abc1234".into()),
+            profile: "release".into(),
+            timing,
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let lines = [entry(1, 1e6), entry(2, 2e6)]
+            .iter()
+            .map(|e| JsonValue::from(e).stringify().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let history = History::from_lines(&lines);
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.entries[1].timestamp, 2);
+        assert_eq!(history.entries[1].commit.as_deref(), Some("abc1234"));
+        assert_eq!(
+            history.entries[1].timing.part_1,
+            Some(PartTiming::solved(2e6, 10))
+        );
+        assert_eq!(history.for_day(day!(6)).len(), 2);
+        assert_eq!(history.for_day(day!(7)).len(), 0);
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let line = JsonValue::from(&entry(1, 1e6)).stringify().unwrap();
+        let history = History::from_lines(&format!("{line}\n{{}}\nnot json\n\n"));
+        assert_eq!(history.entries.len(), 1);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_029_200), "2024-12-01 05:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+}
@@ -0,0 +1,24 @@
+/// Decodes the HTML entities that occur in puzzle descriptions, for the built-in client and the `examples` command.
+/// `&amp;` is decoded last so it cannot form new entities.
+pub fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::decode_entities;
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &quot;c&quot;"), "a <b> \"c\"");
+        assert_eq!(decode_entities("&amp;lt;"), "&lt;");
+    }
+}
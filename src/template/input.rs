@@ -0,0 +1,182 @@
+/// Selects the input a solution runs against, from the `--input` and `--example` command-line flags.
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{env, fs};
+
+use crate::template::commands::CommandError;
+use crate::template::{data_dir, Puzzle};
+
+/// Where a solution reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/inputs`.
+    #[default]
+    Puzzle,
+    /// `--input <path>`: an arbitrary file.
+    Path(String),
+    /// `--input -`: standard input.
+    Stdin,
+    /// `--example [N]`: the example in `data/examples`, or its `DD-N.txt` variant.
+    Example(Option<u8>),
+}
+
+impl InputSource {
+    /// Read the source from the command-line arguments of the current process.
+    pub fn from_args() -> Self {
+        Self::parse(&env::args().collect::<Vec<_>>())
+    }
+
+    fn parse(args: &[String]) -> Self {
+        if let Some(i) = args.iter().position(|x| x == "--input") {
+            return match args.get(i + 1).map(String::as_str) {
+                Some("-") => Self::Stdin,
+                Some(path) => Self::Path(path.into()),
+                None => CommandError::Usage(
+                    "unexpected command-line input. Format: --input <path>".into(),
+                )
+                .exit(),
+            };
+        }
+
+        if let Some(i) = args.iter().position(|x| x == "--example") {
+            return Self::Example(args.get(i + 1).and_then(|x| x.parse().ok()));
+        }
+
+        Self::Puzzle
+    }
+
+    /// Arguments that select this source when passed to a solution bin.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Path(path) => vec!["--input".into(), path.clone()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+        }
+    }
+
+    /// Whether this is the puzzle input, i.e. answers can be submitted and checked.
+    pub fn is_puzzle_input(&self) -> bool {
+        *self == Self::Puzzle
+    }
+
+    /// The file this source reads from, or `None` for stdin.
+    pub fn path(&self, puzzle: Puzzle) -> Option<PathBuf> {
+        match self {
+            Self::Puzzle => Some(puzzle.data_path("inputs")),
+            Self::Path(path) => Some(path.into()),
+            Self::Stdin => None,
+            Self::Example(None) => Some(puzzle.data_path("examples")),
+            Self::Example(Some(n)) => Some(
+                data_dir(puzzle.year)
+                    .join("examples")
+                    .join(format!("{}-{n}.txt", puzzle.day)),
+            ),
+        }
+    }
+
+    pub fn read(&self, puzzle: Puzzle) -> Result<String, io::Error> {
+        match self.path(puzzle) {
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Puzzle => write!(f, "the puzzle input"),
+            Self::Path(path) => write!(f, "\"{path}\""),
+            Self::Stdin => write!(f, "stdin"),
+            Self::Example(None) => write!(f, "the example"),
+            Self::Example(Some(n)) => write!(f, "example {n}"),
+        }
+    }
+}
+
+/// Read the input of `puzzle` from the source selected on the command-line.
+pub fn read_input(puzzle: Puzzle) -> String {
+    let source = InputSource::from_args();
+
+    match source.read(puzzle) {
+        Ok(input) => input,
+        Err(e) => {
+            let context = match source.path(puzzle) {
+                Some(path) => format!(
+                    "could not read input from {source} (\"{}\")",
+                    path.display()
+                ),
+                None => format!("could not read input from {source}"),
+            };
+            CommandError::Io(context, e).exit()
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+    use crate::day;
+    use crate::template::Puzzle;
+
+    fn parse(args: &str) -> InputSource {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        InputSource::parse(&args)
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(parse("01 --time"), InputSource::Puzzle);
+        assert_eq!(
+            parse("01 --input other.txt"),
+            InputSource::Path("other.txt".into())
+        );
+        assert_eq!(parse("01 --input -"), InputSource::Stdin);
+        assert_eq!(parse("01 --example"), InputSource::Example(None));
+        assert_eq!(parse("01 --example --time"), InputSource::Example(None));
+        assert_eq!(parse("01 --example 2"), InputSource::Example(Some(2)));
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Path("a b.txt".into()),
+            InputSource::Stdin,
+            InputSource::Example(None),
+            InputSource::Example(Some(3)),
+        ] {
+            let mut args = vec!["01".to_string()];
+            args.extend(source.to_args());
+            assert_eq!(InputSource::parse(&args), source);
+        }
+    }
+
+    #[test]
+    fn returns_errors_for_missing_files() {
+        let puzzle = Puzzle::new(Some(1994), day!(1));
+
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some(2)),
+        ] {
+            let error = source.read(puzzle).unwrap_err();
+            assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+        }
+
+        assert_eq!(
+            InputSource::Example(Some(2)).path(puzzle),
+            Some("data/1994/examples/01-2.txt".into())
+        );
+    }
+}
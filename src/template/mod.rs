@@ -1,14 +1,33 @@
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
+#[cfg(feature = "aoc-client")]
+pub mod aoc_client;
+pub mod batch;
+pub mod calendar;
 pub mod commands;
+pub mod examples;
+pub mod export;
+pub mod input;
+pub mod registry;
 pub mod runner;
 
+pub use calendar::{Calendar, Clock, FixedClock, SystemClock};
 pub use day::*;
+pub use puzzle::*;
 
+mod answers;
+mod compare;
 mod day;
+mod example_manifest;
+mod history;
+mod html;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -16,53 +35,162 @@ pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Accepts a [`Day`] of the flat layout or a year-qualified [`Puzzle`].
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<Puzzle>) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path(folder));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<Puzzle>, part: u8) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(puzzle.year))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
 ///
+/// The day may be qualified with a year, e.g. `solution!(2023 / 5)`, for solutions in the multi-year layout.
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse = <function>`, e.g. `solution!(5, parse = parse_input)`, the input is parsed once and both parts
+/// receive a reference to the parsed value. The parse step is timed separately from the parts.
+/// A single part is selected before the parse function, e.g. `solution!(5, 1, parse = parse_input)`.
+///
+/// In tests, every example listed in `data/examples/DD.toml` becomes a test, see [`crate::template::examples`].
 #[macro_export]
 macro_rules! solution {
+    ($year:literal / $day:expr, parse = $parse:expr) => {
+        $crate::solution!(@parse Some($year), $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@parse None, $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($year:literal / $day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@parse Some($year), $day, $parse, [part_one, 1]);
+    };
+    ($year:literal / $day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@parse Some($year), $day, $parse, [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@parse None, $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@parse None, $day, $parse, [part_two, 2]);
+    };
+    ($year:literal / $day:expr) => {
+        $crate::solution!(@impl Some($year), $day, [part_one, 1] [part_two, 2]);
+    };
+    ($year:literal / $day:expr, 1) => {
+        $crate::solution!(@impl Some($year), $day, [part_one, 1]);
+    };
+    ($year:literal / $day:expr, 2) => {
+        $crate::solution!(@impl Some($year), $day, [part_two, 2]);
+    };
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl None, $day, [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl None, $day, [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl None, $day, [part_two, 2]);
+    };
+
+    (@impl $year:expr, $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@consts $year, $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+
+            if let Some(dir) = $crate::template::batch::inputs_dir_from_args() {
+                $crate::template::batch::run_inputs_dir(&dir, PUZZLE, SOLUTION.run);
+                return;
+            }
+
+            let input = $crate::template::input::read_input(PUZZLE);
+            let results = [$( run_part($func, &input, PUZZLE, $part), )*];
+            exit_on_failure(&results);
+        }
+
+        /// Entry point used to run this solution in-process, see [`$crate::template::registry`].
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            puzzle: PUZZLE,
+            run: |input, options| {
+                use $crate::template::runner::*;
+                vec![$( run_part_with_options($func, input, PUZZLE, $part, options), )*]
+            },
+        };
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@parse $year:expr, $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@consts $year, $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+
+            if let Some(dir) = $crate::template::batch::inputs_dir_from_args() {
+                $crate::template::batch::run_inputs_dir(&dir, PUZZLE, SOLUTION.run);
+                return;
+            }
+
+            let input = $crate::template::input::read_input(PUZZLE);
+            let (parsed, record) = run_parse_with_options($parse, &input, &RunOptions::from_args());
+            let mut results = vec![record];
+
+            if let Some(parsed) = parsed {
+                $( results.push(run_part($func, &parsed, PUZZLE, $part)); )*
+            }
+
+            exit_on_failure(&results);
+        }
+
+        /// Entry point used to run this solution in-process, see [`$crate::template::registry`].
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            puzzle: PUZZLE,
+            run: |input, options| {
+                use $crate::template::runner::*;
+
+                let (parsed, record) = run_parse_with_options($parse, input, options);
+                let mut results = vec![record];
+
+                if let Some(parsed) = parsed {
+                    $( results.push(run_part_with_options($func, &parsed, PUZZLE, $part, options)); )*
+                }
+
+                results
+            },
+        };
+    };
+
+    (@consts $year:expr, $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current day, qualified with its year if any.
+        const PUZZLE: $crate::template::Puzzle = $crate::template::Puzzle::new($year, DAY);
+
+        const _: () = assert!(PUZZLE.is_in_event(), "day is not part of the event of its year");
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+        /// One test per example in the day's manifest, generated by `build.rs`.
+        #[cfg(test)]
+        mod example_manifest {
+            include!(concat!(env!("OUT_DIR"), "/examples/", env!("CARGO_BIN_NAME"), ".rs"));
         }
     };
 }
@@ -0,0 +1,183 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::template::{Calendar, Day, DayFromStrError};
+
+/// A day of advent, optionally qualified with the year of its event.
+///
+/// Days without a year use the flat layout of a single-event repository:
+/// `data/<folder>/DD.txt` and `src/bin/DD.rs`.
+/// Days of a specific year live in `data/<year>/<folder>/DD.txt` and `src/bin/<year>-DD.rs`.
+///
+/// # Display
+/// This value displays as `DD` or `YYYY/DD`.
+///
+/// ```
+/// # use advent_of_code::template::{Day, Puzzle};
+/// let puzzle: Puzzle = "2023/5".parse().unwrap();
+/// assert_eq!(puzzle.to_string(), "2023/05");
+/// assert_eq!(puzzle.bin_name(), "2023-05");
+/// assert_eq!(Puzzle::from(Day::new(5).unwrap()).to_string(), "05");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Option<u16>,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub const fn new(year: Option<u16>, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Whether the day is part of the event of its year. Days without a year are not checked.
+    pub const fn is_in_event(&self) -> bool {
+        match self.year {
+            Some(year) => Calendar::new(year).contains(self.day),
+            None => true,
+        }
+    }
+
+    /// Name of the solution binary of this puzzle.
+    pub fn bin_name(&self) -> String {
+        match self.year {
+            Some(year) => format!("{year}-{}", self.day),
+            None => self.day.to_string(),
+        }
+    }
+
+    /// Path to the source file of the solution binary.
+    pub fn bin_path(&self) -> String {
+        format!("./src/bin/{}.rs", self.bin_name())
+    }
+
+    /// Path to the file of this puzzle in a data folder, e.g. `data/2023/inputs/05.txt`.
+    pub fn data_path(&self, folder: &str) -> PathBuf {
+        data_dir(self.year)
+            .join(folder)
+            .join(format!("{}.txt", self.day))
+    }
+}
+
+/// Directory that holds the data of a year, or of the flat layout if `year` is `None`.
+pub fn data_dir(year: Option<u16>) -> PathBuf {
+    let data = PathBuf::from("data");
+    match year {
+        Some(year) => data.join(year.to_string()),
+        None => data,
+    }
+}
+
+impl From<Day> for Puzzle {
+    fn from(day: Day) -> Self {
+        Self::new(None, day)
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{year}/{}", self.day),
+            None => write!(f, "{}", self.day),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Puzzle {
+    type Err = PuzzleFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let puzzle = match s.split_once('/') {
+            Some((year, day)) => {
+                let year = year
+                    .parse()
+                    .ok()
+                    .filter(|year| *year >= 2015)
+                    .ok_or(PuzzleFromStrError::Year)?;
+                Self::new(Some(year), day.parse()?)
+            }
+            None => Self::new(None, s.parse()?),
+        };
+
+        let calendar = Calendar::resolve(puzzle.year);
+        if calendar.contains(puzzle.day) {
+            Ok(puzzle)
+        } else {
+            Err(PuzzleFromStrError::NotInEvent(calendar))
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Puzzle`].
+#[derive(Debug)]
+pub enum PuzzleFromStrError {
+    Year,
+    Day(DayFromStrError),
+    NotInEvent(Calendar),
+}
+
+impl From<DayFromStrError> for PuzzleFromStrError {
+    fn from(e: DayFromStrError) -> Self {
+        PuzzleFromStrError::Day(e)
+    }
+}
+
+impl Error for PuzzleFromStrError {}
+
+impl Display for PuzzleFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleFromStrError::Year => f.write_str("expecting a year of 2015 or later"),
+            PuzzleFromStrError::Day(e) => write!(f, "{e}"),
+            PuzzleFromStrError::NotInEvent(calendar) => write!(
+                f,
+                "the {} event only has {} days",
+                calendar.year(),
+                calendar.day_count()
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Puzzle;
+    use crate::day;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_puzzles() {
+        assert_eq!("5".parse::<Puzzle>().unwrap(), Puzzle::new(None, day!(5)));
+        assert_eq!(
+            "2023/05".parse::<Puzzle>().unwrap(),
+            Puzzle::new(Some(2023), day!(5))
+        );
+        assert!("2023/26".parse::<Puzzle>().is_err());
+        assert!("23/5".parse::<Puzzle>().is_err());
+        assert!("2025/13".parse::<Puzzle>().is_err());
+        assert!("2025/12".parse::<Puzzle>().is_ok());
+    }
+
+    #[test]
+    fn resolves_paths() {
+        let flat = Puzzle::new(None, day!(1));
+        assert_eq!(flat.bin_path(), "./src/bin/01.rs");
+        assert_eq!(
+            flat.data_path("inputs"),
+            PathBuf::from("data/inputs/01.txt")
+        );
+
+        let puzzle = Puzzle::new(Some(2023), day!(1));
+        assert_eq!(puzzle.bin_path(), "./src/bin/2023-01.rs");
+        assert_eq!(
+            puzzle.data_path("examples"),
+            PathBuf::from("data/2023/examples/01.txt")
+        );
+    }
+}
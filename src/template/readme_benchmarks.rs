@@ -1,12 +1,23 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::alloc::format_bytes;
+use crate::template::history::History;
+use crate::template::stats::to_duration;
+use crate::template::timings::{Timing, Timings};
+use crate::template::Puzzle;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Marker around the table of `year`. Each year of a multi-year repository gets its own table.
+fn marker(year: Option<u16>) -> String {
+    match year {
+        Some(year) => format!("<!--- benchmarking table {year} --->"),
+        None => MARKER.into(),
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -25,13 +36,8 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,77 +58,328 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Optional columns and summaries of the benchmark table.
+///
+/// They are selected with a comma-separated list in the `AOC_BENCHMARK_COLUMNS` environment variable,
+/// e.g. `parse,p95,memory,samples,change,subtotal,slowest`. Without it, the parse and memory columns are
+/// shown once any day recorded them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Columns {
+    /// Time of the shared parse step.
+    pub parse: bool,
+    /// The 95th percentile next to the median of each part.
+    pub p95: bool,
+    /// Peak memory, allocated bytes and allocation count.
+    pub memory: bool,
+    /// Number of samples each part was benched with.
+    pub samples: bool,
+    /// Change of each part since the previous stored run in the benchmark history.
+    pub change: bool,
+    /// A row with the sum of every timing column.
+    pub subtotal: bool,
+    /// The slowest days below the table.
+    pub slowest: bool,
+}
+
+/// Name of the environment variable that selects the [`Columns`], e.g. in `.cargo/config.toml`.
+static COLUMNS_ENV: &str = "AOC_BENCHMARK_COLUMNS";
+
+/// Number of days listed in the slowest days summary.
+const SLOWEST_DAYS: usize = 3;
+
+impl Columns {
+    /// Columns selected in the environment, or the ones with recorded data if none are selected.
+    fn from_env(timings: &Timings) -> Self {
+        match env::var(COLUMNS_ENV) {
+            Ok(list) => Self::parse_list(&list).unwrap_or_else(|e| {
+                eprintln!("Ignoring {COLUMNS_ENV}: {e}");
+                Self::detect(timings)
+            }),
+            Err(_) => Self::detect(timings),
+        }
+    }
+
+    /// The parse column is shown once a day uses a shared parse step,
+    /// memory columns once a day was timed with the `count-allocs` feature.
+    fn detect(timings: &Timings) -> Self {
+        Self {
+            parse: timings.data.iter().any(|t| t.parse.is_some()),
+            memory: timings.data.iter().any(|t| t.memory().is_some()),
+            ..Self::default()
+        }
+    }
+
+    fn parse_list(list: &str) -> Result<Self, String> {
+        let mut columns = Self::default();
+
+        for name in list.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+            let column = match name {
+                "parse" => &mut columns.parse,
+                "p95" => &mut columns.p95,
+                "memory" => &mut columns.memory,
+                "samples" => &mut columns.samples,
+                "change" => &mut columns.change,
+                "subtotal" => &mut columns.subtotal,
+                "slowest" => &mut columns.slowest,
+                _ => return Err(format!("unknown column \"{name}\".")),
+            };
+            *column = true;
+        }
+
+        Ok(columns)
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    year: Option<u16>,
+    timings: Timings,
+    total_millis: f64,
+    columns: Columns,
+    history: &History,
+) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Benchmarks"),
+        None => format!("{prefix} Benchmarks"),
+    };
+
+    let mut header_row = String::from("| Day |");
+    let mut separator_row = String::from("| :---: |");
+
+    if columns.parse {
+        header_row.push_str(" Parse |");
+        separator_row.push_str(" :---: |");
+    }
+
+    header_row.push_str(" Part 1 | Part 2 |");
+    separator_row.push_str(" :---: | :---:  |");
+
+    if columns.samples {
+        header_row.push_str(" Samples |");
+        separator_row.push_str(" :---: |");
+    }
+
+    if columns.change {
+        header_row.push_str(" Change |");
+        separator_row.push_str(" :---: |");
+    }
+
+    if columns.memory {
+        header_row.push_str(" Peak memory | Allocated | Allocations |");
+        separator_row.push_str(" :---: | :---: | :---: |");
+    }
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker(year),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        header_row,
+        separator_row,
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    for timing in &timings.data {
+        let path = Puzzle::new(year, timing.day).bin_path();
+        let mut line = format!("| [Day {}]({}) |", timing.day.into_inner(), path);
+
+        let parts: &[u8] = if columns.parse { &[0, 1, 2] } else { &[1, 2] };
+        for part in parts {
+            line.push_str(&format!(" {} |", format_part(timing, *part, columns)));
+        }
+
+        if columns.samples {
+            let samples = [&timing.part_1, &timing.part_2].map(|part| match part {
+                Some(p) if p.is_solved() => format!("`{}`", p.samples),
+                _ => "-".into(),
+            });
+            line.push_str(&format!(" {} |", samples.join(" / ")));
+        }
+
+        if columns.change {
+            line.push_str(&format!(" {} |", format_change(timing, history)));
+        }
+
+        if columns.memory {
+            line.push_str(&match timing.memory() {
+                Some(m) => format!(
+                    " `{}` | `{}` | `{}` |",
+                    format_bytes(m.peak_bytes),
+                    format_bytes(m.total_bytes),
+                    m.allocations
+                ),
+                None => " - | - | - |".into(),
+            });
+        }
+
+        lines.push(line);
+    }
+
+    if columns.subtotal {
+        let mut line = String::from("| **Total** |");
+
+        let parts: &[u8] = if columns.parse { &[0, 1, 2] } else { &[1, 2] };
+        for part in parts {
+            let nanos: f64 = timings
+                .data
+                .iter()
+                .filter_map(|t| t.part_nanos(*part))
+                .sum();
+            line.push_str(&format!(" **{:.1?}** |", to_duration(nanos)));
+        }
+
+        let empty_columns = usize::from(columns.samples)
+            + usize::from(columns.change)
+            + if columns.memory { 3 } else { 0 };
+        line.push_str(&" |".repeat(empty_columns));
+
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    if columns.slowest {
+        let mut slowest: Vec<_> = timings
+            .data
+            .iter()
+            .filter(|t| t.total_nanos > 0.0)
+            .collect();
+        slowest.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+
+        let days: Vec<String> = slowest
+            .iter()
+            .take(SLOWEST_DAYS)
+            .map(|t| {
+                format!(
+                    "[Day {}]({}) (`{:.1?}`)",
+                    t.day.into_inner(),
+                    Puzzle::new(year, t.day).bin_path(),
+                    to_duration(t.total_nanos)
+                )
+            })
+            .collect();
+
+        if !days.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("**Slowest days:** {}", days.join(", ")));
+        }
+    }
+
+    lines.push(marker(year));
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Median of a part, followed by its 95th percentile if selected. Part `0` is the shared parse step.
+/// Parts that did not finish before the day was killed are marked as timed out.
+fn format_part(timing: &Timing, part: u8, columns: Columns) -> String {
+    let Some(part_timing) = timing.part(part) else {
+        return "`-`".into();
+    };
+
+    let median = format!("`{part_timing}`");
+
+    match part_timing.stats {
+        Some(stats) if columns.p95 && part != 0 => {
+            format!("{median} (p95 `{:.1?}`)", to_duration(stats.p95))
+        }
+        _ => median,
+    }
+}
+
+/// Change of both parts between the last two stored runs of a day.
+fn format_change(timing: &Timing, history: &History) -> String {
+    let entries = history.for_day(timing.day);
+
+    let [.., previous, latest] = entries.as_slice() else {
+        return "-".into();
+    };
+
+    [1, 2]
+        .map(|part| {
+            match (
+                previous.timing.part_nanos(part),
+                latest.timing.part_nanos(part),
+            ) {
+                (Some(prev), Some(nanos)) if prev > 0.0 => {
+                    format!("`{:+.1}%`", (nanos - prev) / prev * 100.0)
+                }
+                _ => "-".into(),
+            }
+        })
+        .join(" / ")
+}
+
+fn update_content(
+    s: &mut String,
+    year: Option<u16>,
+    timings: Timings,
+    total_millis: f64,
+    columns: Columns,
+    history: &History,
+) -> Result<(), Error> {
+    let marker = marker(year);
+
+    // the table of a year that was never benchmarked is appended to the end of the readme.
+    if year.is_some() && !s.contains(&marker) {
+        if !s.ends_with('\n') {
+            s.push('\n');
+        }
+        s.push_str(&format!("\n{marker}\n{marker}\n"));
+    }
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", year, timings, total_millis, columns, history);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Option<u16>, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let columns = Columns::from_env(&timings);
+
+    // the history is only needed to compute changes.
+    let history = if columns.change {
+        History::read_from_file(year)
+    } else {
+        History::default()
+    };
+
+    update_content(&mut readme, year, timings, total_millis, columns, &history)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, Columns, Error, MARKER};
+    use crate::template::alloc::MemoryStats;
+    use crate::template::history::{History, HistoryEntry};
+    use crate::template::stats::BenchStats;
+    use crate::template::timings::{PartTiming, Timing, Timings};
+    use crate::{day, template::Day};
+
+    /// Update the table with the default columns and no history.
+    fn update(s: &mut String, year: Option<u16>, timings: Timings) -> Result<(), Error> {
+        let columns = Columns::detect(&timings);
+        update_content(s, year, timings, 190.0, columns, &History::default())
+    }
+
+    fn timing(day: Day, part_1_millis: f64, part_2_millis: f64, total_nanos: f64) -> Timing {
+        let mut timing = Timing::new(day);
+        timing.part_1 = Some(PartTiming::solved(part_1_millis * 1e6, 1));
+        timing.part_2 = Some(PartTiming::solved(part_2_millis * 1e6, 1));
+        timing.total_nanos = total_nanos;
+        timing
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
-                },
+                timing(day!(1), 10.0, 20.0, 3e+10),
+                timing(day!(2), 30.0, 40.0, 7e+10),
+                timing(day!(4), 40.0, 50.0, 9e+10),
             ],
         }
     }
@@ -131,28 +388,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, None, get_mock_timings()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, None, get_mock_timings()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update(&mut s, None, get_mock_timings()).unwrap();
+        assert!(s.contains("## Benchmarks"));
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, None, get_mock_timings()).unwrap();
+        update(&mut s, None, get_mock_timings()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +417,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, None, get_mock_timings()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +426,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +437,130 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn formats_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().memory = Some(MemoryStats {
+            peak_bytes: 1024,
+            total_bytes: 2048,
+            allocations: 2,
+        });
+        timings.data[0].part_2.as_mut().unwrap().memory = Some(MemoryStats {
+            peak_bytes: 512,
+            total_bytes: 1024,
+            allocations: 1,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update(&mut s, None, timings).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Peak memory | Allocated | Allocations |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `1.0 KiB` | `3.0 KiB` | `3` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | - | - | - |"));
+    }
+
+    #[test]
+    fn formats_parse_column() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some(PartTiming::solved(5e6, 1));
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update(&mut s, None, timings).unwrap();
+
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |"));
+    }
+
+    #[test]
+    fn marks_timed_out_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = Some(PartTiming::timed_out());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update(&mut s, None, timings).unwrap();
+
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `40.0ms` | `timed out` |"));
+    }
+
+    #[test]
+    fn appends_tables_per_year() {
+        let mut s = format!("foo\n{}{}\n", MARKER, MARKER);
+        update(&mut s, None, get_mock_timings()).unwrap();
+        update(&mut s, Some(2023), get_mock_timings()).unwrap();
+        update(&mut s, Some(2023), get_mock_timings()).unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("<!--- benchmarking table 2023 --->").count(), 2);
+        assert_eq!(s.matches("## 2023 Benchmarks").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |"));
+    }
+
+    #[test]
+    fn parses_column_lists() {
+        assert_eq!(
+            Columns::parse_list("p95, subtotal,slowest").unwrap(),
+            Columns {
+                p95: true,
+                subtotal: true,
+                slowest: true,
+                ..Columns::default()
+            }
+        );
+        assert_eq!(Columns::parse_list("").unwrap(), Columns::default());
+        assert!(Columns::parse_list("parse,speed").is_err());
+    }
+
+    #[test]
+    fn formats_optional_columns() {
+        let stats = |median: f64, p95: f64| BenchStats {
+            samples: 10,
+            mean: median,
+            min: median,
+            median,
+            p95,
+            std_dev: 0.0,
+            outliers: 0,
+        };
+
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1 = Some(PartTiming {
+            stats: Some(stats(10_000_000.0, 12_000_000.0)),
+            ..PartTiming::solved(10_000_000.0, 10)
+        });
+
+        let mut previous = timings.data[0].clone();
+        previous.part_1 = Some(PartTiming::solved(8e6, 1));
+        previous.part_2 = Some(PartTiming::solved(25e6, 1));
+
+        let history = History {
+            entries: [previous, timings.data[0].clone()]
+                .into_iter()
+                .map(|timing| HistoryEntry {
+                    timestamp: 0,
+                    commit: None,
+                    profile: "release".into(),
+                    timing,
+                })
+                .collect(),
+        };
+
+        let columns = Columns::parse_list("p95,samples,change,subtotal,slowest").unwrap();
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, None, timings, 190.0, columns, &history).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Samples | Change |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` (p95 `12.0ms`) | `20.0ms` | `10` / `1` | `+25.0%` / `-20.0%` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | `1` / `1` | - |"));
+        assert!(s.contains("| **Total** | **80.0ms** | **110.0ms** | | |"));
+        assert!(s.contains(
+            "**Slowest days:** [Day 4](./src/bin/04.rs) (`90.0s`), [Day 2](./src/bin/02.rs) (`70.0s`), [Day 1](./src/bin/01.rs) (`30.0s`)"
+        ));
+    }
 }
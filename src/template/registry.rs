@@ -0,0 +1,25 @@
+/// Library-level registry of solutions that are compiled into the running binary.
+///
+/// The `advent_of_code` binary registers every scaffolded day when built with the `in-process` feature.
+/// `run_multi` then runs registered days directly instead of spawning `cargo run --bin <day>` for each.
+use std::sync::OnceLock;
+
+use crate::template::runner::Solution;
+use crate::template::Puzzle;
+
+static SOLUTIONS: OnceLock<&'static [Solution]> = OnceLock::new();
+
+/// Registers the solutions of the current binary. Only the first call has an effect.
+pub fn register(solutions: &'static [Solution]) {
+    let _ = SOLUTIONS.set(solutions);
+}
+
+/// All registered solutions. Empty if none were registered.
+pub fn registered() -> &'static [Solution] {
+    SOLUTIONS.get().copied().unwrap_or_default()
+}
+
+/// The registered solution for `puzzle`, if any.
+pub fn find(puzzle: Puzzle) -> Option<&'static Solution> {
+    registered().iter().find(|s| s.puzzle == puzzle)
+}
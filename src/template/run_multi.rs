@@ -1,40 +1,143 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    env,
+    fmt::Display,
+    fs, io,
+    process::ExitStatus,
+    sync::{mpsc, Mutex},
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::CheckStatus;
+use crate::template::runner::{PartResult, PartStatus, RunOptions, Solution};
+use crate::template::{registry, Day, Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{PartTiming, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Run a set of days of `year` and print their output in day order.
+///
+/// With `jobs` greater than one, days are run concurrently as child processes and their output is buffered
+/// until all earlier days have been printed. Timed runs should pass `1` so benchmarks are not disturbed.
+///
+/// Days that run longer than `timeout` are killed and the remaining days continue.
+/// Solutions are always run as child processes when a timeout is set, as a running solution cannot be
+/// interrupted in-process, and when `jobs` is greater than one, as the output of a solution can only be
+/// buffered per day if it runs in its own process.
+pub fn run_multi(
+    year: Option<u16>,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    check: bool,
+    timeout: Option<Duration>,
+) -> RunSummary {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
+    let mut failed_checks = 0;
+    let mut timed_out = 0;
+    let mut failed = 0;
+    let mut panicked = 0;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let puzzles: Vec<Puzzle> = all_days()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+        .map(|day| Puzzle::new(year, day))
+        .collect();
+
+    let mut record_results = |puzzle: Puzzle, run: Result<SolutionRun, Error>| {
+        let run = match run {
+            Ok(run) => run,
+            Err(e) => {
+                // NOTE: a day that could not be run counts as failed, the remaining days continue.
+                eprintln!("Could not run day {puzzle}: {e}");
+                failed += 1;
+                return;
             }
-            need_space = true;
+        };
+        let results = &run.results;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        failed_checks += results
+            .iter()
+            .filter(|r| r.check == Some(CheckStatus::Incorrect))
+            .count();
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        failed += results
+            .iter()
+            .filter(|r| r.status == PartStatus::Failed)
+            .count();
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+        panicked += results
+            .iter()
+            .filter(|r| r.status == PartStatus::Panicked)
+            .count();
+
+        if let Some(status) = run.status.filter(|_| run.is_failed()) {
+            failed += 1;
+            println!("Failed with {status}.");
+            return;
+        }
+
+        if run.timed_out {
+            timed_out += 1;
+            println!(
+                "timed out after {}s",
+                timeout.map_or(0.0, |t| t.as_secs_f64())
+            );
+        } else if results.is_empty() {
+            println!("Not solved.");
+            return;
+        }
 
-    if is_timed {
+        let mut val = child_commands::collect_timing(results, puzzle.day);
+        if run.timed_out {
+            // parts that did not report before the day was killed.
+            val.part_1 = val.part_1.or(Some(PartTiming::timed_out()));
+            val.part_2 = val.part_2.or(Some(PartTiming::timed_out()));
+        }
+        timings.push(val);
+    };
+
+    if jobs > 1 {
+        run_parallel(
+            &puzzles,
+            is_timed,
+            is_release,
+            check,
+            timeout,
+            jobs,
+            |i, puzzle, run| {
+                print_day_header(puzzle, i > 0);
+                if let Ok(run) = &run {
+                    run.output.print();
+                }
+                record_results(puzzle, run);
+            },
+        );
+    } else {
+        for (i, &puzzle) in puzzles.iter().enumerate() {
+            print_day_header(puzzle, i > 0);
+
+            let run = match registry::find(puzzle).filter(|_| timeout.is_none()) {
+                Some(solution) => {
+                    run_in_process(solution, is_timed, check).map(|results| SolutionRun {
+                        results,
+                        ..SolutionRun::default()
+                    })
+                }
+                None => child_commands::run_solution(
+                    puzzle, is_timed, is_release, check, false, timeout,
+                ),
+            };
+
+            record_results(puzzle, run);
+        }
+    }
+
+    let timings = if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,7 +146,128 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    if check {
+        if failed_checks == 0 {
+            println!("\n{ANSI_BOLD}Check:{ANSI_RESET} all stored answers match.");
+        } else {
+            println!("\n{ANSI_BOLD}Check:{ANSI_RESET} {failed_checks} part(s) did not match their stored answer.");
+        }
+    }
+
+    RunSummary {
+        timings,
+        failed_checks,
+        timed_out,
+        failed,
+        panicked,
+    }
+}
+
+/// Outcome of [`run_multi`].
+pub struct RunSummary {
+    /// Timings of all days, present if the run was timed.
+    pub timings: Option<Timings>,
+    /// Number of parts that did not match their stored answer.
+    pub failed_checks: usize,
+    /// Number of days that were killed after exceeding the timeout.
+    pub timed_out: usize,
+    /// Number of parts that returned an error, plus days whose solution could not be started or exited
+    /// with an error before reporting any part.
+    pub failed: usize,
+    /// Number of parts that panicked.
+    pub panicked: usize,
+}
+
+fn print_day_header(puzzle: Puzzle, need_space: bool) {
+    if need_space {
+        println!();
     }
+    println!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Run `puzzles` on up to `jobs` worker threads, each driving one child process at a time.
+/// `on_finished` is called in the order of `puzzles`, as soon as a day and all days before it are done.
+fn run_parallel(
+    puzzles: &[Puzzle],
+    is_timed: bool,
+    is_release: bool,
+    check: bool,
+    timeout: Option<Duration>,
+    jobs: usize,
+    mut on_finished: impl FnMut(usize, Puzzle, Result<SolutionRun, Error>),
+) {
+    let next_index = Mutex::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(puzzles.len()) {
+            let sender = sender.clone();
+            let next_index = &next_index;
+
+            scope.spawn(move || loop {
+                let i = {
+                    let mut next_index = next_index.lock().unwrap();
+                    let i = *next_index;
+                    *next_index += 1;
+                    i
+                };
+
+                let Some(&puzzle) = puzzles.get(i) else {
+                    break;
+                };
+
+                let run = child_commands::run_solution(
+                    puzzle, is_timed, is_release, check, true, timeout,
+                );
+
+                if sender.send((i, run)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (i, run) in receiver {
+            pending.insert(i, run);
+
+            while let Some(run) = pending.remove(&next_to_print) {
+                on_finished(next_to_print, puzzles[next_to_print], run);
+                next_to_print += 1;
+            }
+        }
+    });
+}
+
+/// Run a solution that is compiled into the current binary.
+fn run_in_process(
+    solution: &Solution,
+    is_timed: bool,
+    check: bool,
+) -> Result<Vec<PartResult>, Error> {
+    let path = env::current_dir()?.join(solution.puzzle.data_path("inputs"));
+
+    let input = fs::read_to_string(&path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("could not read input \"{}\": {e}", path.display()),
+        )
+    })?;
+
+    Ok((solution.run)(
+        &input,
+        &RunOptions {
+            is_timed,
+            emit_json: false,
+            check,
+        },
+    ))
 }
 
 #[allow(dead_code)]
@@ -53,51 +277,130 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output of the solution."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Output of a solution bin that was buffered instead of being forwarded.
+#[derive(Debug, Default)]
+pub struct CapturedOutput {
+    pub stdout: Vec<String>,
+    pub stderr: Vec<String>,
+}
+
+impl CapturedOutput {
+    pub fn print(&self) {
+        self.stdout.iter().for_each(|line| println!("{line}"));
+        self.stderr.iter().for_each(|line| eprintln!("{line}"));
+    }
+}
+
+/// Result records and output of a single run of a solution bin.
+#[derive(Debug, Default)]
+pub struct SolutionRun {
+    pub results: Vec<PartResult>,
+    pub output: CapturedOutput,
+    /// Whether the bin was killed after exceeding the timeout. `results` only holds the parts that finished.
+    pub timed_out: bool,
+    /// Exit status of the child process, `None` if the solution was not run as a child.
+    pub status: Option<ExitStatus>,
+}
+
+impl SolutionRun {
+    /// Whether the bin exited with an error before reporting any part, e.g. because it did not build
+    /// or could not read its input.
+    pub fn is_failed(&self) -> bool {
+        !self.timed_out && self.results.is_empty() && self.status.is_some_and(|s| !s.success())
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their result records.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{CapturedOutput, Error, SolutionRun};
+    use crate::template::alloc;
+    use crate::template::runner::{PartResult, PartStatus, PARSE_PART};
+    use crate::template::timings::{PartTiming, TimingStatus};
+    use crate::template::{Day, Puzzle};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
-        thread,
+        process::{Child, Command, Stdio},
+        sync::{
+            mpsc::{self, RecvTimeoutError},
+            Arc, Mutex,
+        },
+        thread::{self, JoinHandle},
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day and collect its result records.
+    /// Its output is forwarded as it arrives, or returned in a [`CapturedOutput`] if `capture` is set.
+    /// If the bin runs longer than `timeout`, it is killed.
+    pub fn run_solution(
+        puzzle: Puzzle,
+        is_timed: bool,
+        is_release: bool,
+        check: bool,
+        capture: bool,
+        timeout: Option<Duration>,
+    ) -> Result<SolutionRun, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok(SolutionRun::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = puzzle.bin_name();
+        let mut args = vec!["--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
         }
 
+        if alloc::IS_ENABLED {
+            // the counting allocator is only installed if the child is built with the feature, too.
+            args.extend(["--features", "count-allocs"]);
+        }
+
+        if timeout.is_some() {
+            // build ahead of the run so compile time does not count towards the timeout.
+            // errors are reported by the run below.
+            Command::new("cargo")
+                .arg("build")
+                .args(&args)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()?;
+        }
+
+        args.insert(0, "run");
+
+        // request structured result records from the child.
+        args.push("--");
+        args.push("--json");
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if check {
+            args.push("--check");
+        }
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward or capture output while grabbing result records.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -108,87 +411,100 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let cmd = Arc::new(Mutex::new(cmd));
+        let watchdog = timeout.map(|timeout| watch(Arc::clone(&cmd), timeout));
+
+        let mut results = vec![];
+        let mut output = CapturedOutput::default();
 
         let thread = thread::spawn(move || {
+            let mut captured = vec![];
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                let line = line.unwrap();
+                if capture {
+                    captured.push(line);
+                } else {
+                    eprintln!("{line}");
+                }
             });
+            captured
         });
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            match PartResult::from_line(&line) {
+                Some(Ok(result)) => results.push(result),
+                Some(Err(e)) => eprintln!("Could not parse result record: {e}"),
+                None if capture => output.stdout.push(line),
+                None => println!("{line}"),
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        output.stderr = thread.join().unwrap();
+
+        let timed_out = watchdog.is_some_and(|(done, handle)| {
+            drop(done);
+            handle.join().unwrap()
+        });
+
+        let status = cmd.lock().unwrap().wait()?;
 
-        Ok(output)
+        Ok(SolutionRun {
+            results,
+            output,
+            timed_out,
+            status: Some(status),
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Kill `child` unless the returned sender is dropped within `timeout`.
+    /// The handle resolves to whether the child was killed.
+    fn watch(child: Arc<Mutex<Child>>, timeout: Duration) -> (mpsc::Sender<()>, JoinHandle<bool>) {
+        let (done, receiver) = mpsc::channel::<()>();
+
+        let handle = thread::spawn(move || {
+            if receiver.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                // `cargo run` execs the solution bin on unix and runs it in a job object on windows,
+                // so killing cargo stops the solution as well.
+                let _ = child.lock().unwrap().kill();
+                true
+            } else {
+                false
+            }
+        });
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        (done, handle)
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    /// Collect the timing of a day from the result records of its parts.
+    pub fn collect_timing(results: &[PartResult], day: Day) -> super::Timing {
+        let mut timings = super::Timing::new(day);
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+        results
+            .iter()
+            .filter(|r| r.status == PartStatus::Solved)
+            .for_each(|r| {
+                let part = Some(PartTiming {
+                    status: TimingStatus::Solved,
+                    nanos: r.nanos,
+                    samples: r.samples,
+                    stats: r.stats,
+                    memory: r.memory,
+                });
+
+                match r.part {
+                    PARSE_PART => timings.parse = part,
+                    1 => timings.part_1 = part,
+                    2 => timings.part_2 = part,
+                    _ => return,
                 }
 
-                timings.total_nanos += nanos;
+                timings.total_nanos += r.nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,53 +521,141 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use std::process::Command;
+
+        use super::collect_timing;
 
         use crate::day;
+        use crate::template::alloc::MemoryStats;
+        use crate::template::run_multi::SolutionRun;
+        use crate::template::runner::{PartResult, PartStatus};
+
+        fn parse(lines: &[&str]) -> Vec<PartResult> {
+            lines
+                .iter()
+                .filter_map(|l| PartResult::from_line(l))
+                .collect::<Result<_, _>>()
+                .unwrap()
+        }
 
         #[test]
         fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
+            let res = collect_timing(
+                &parse(&[
+                    "Part 1: 0 (74.1ns @ 100000 samples)",
+                    r#"::aoc-result::{"part":1,"answer":"0","nanos":74.0,"samples":100000,"status":"solved"}"#,
+                    "Part 2: 10 (74.1ms @ 99999 samples)",
+                    r#"::aoc-result::{"part":2,"answer":"10","nanos":74130000.0,"samples":99999,"status":"solved"}"#,
+                    "",
+                ]),
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap().to_string(), "74.0ns");
+            assert_eq!(res.part_1.unwrap().samples, 100000);
+            assert_eq!(res.part_2.unwrap().to_string(), "74.1ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
+        fn parses_with_patterns_in_answer() {
+            let res = collect_timing(
+                &parse(&[
+                    r#"::aoc-result::{"part":1,"answer":"@ @ @ ( ) ms (1s @ 5 samples)","nanos":2000000000,"samples":5,"status":"solved"}"#,
+                    r#"::aoc-result::{"part":2,"answer":"10s\n(2 samples)\n","nanos":100000000,"samples":1,"status":"solved"}"#,
+                ]),
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().to_string(), "2.0s");
+            assert_eq!(res.part_2.unwrap().to_string(), "100.0ms");
         }
 
         #[test]
         fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
+            let res = collect_timing(
+                &parse(&[
+                    "Part 1: ✖        ",
+                    r#"::aoc-result::{"part":1,"answer":null,"nanos":10,"samples":1,"status":"unsolved"}"#,
+                    "Part 2: ✖        ",
+                    r#"::aoc-result::{"part":2,"answer":null,"nanos":10,"samples":1,"status":"unsolved"}"#,
+                    "",
+                ]),
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
+        }
+
+        #[test]
+        fn parses_parse_step() {
+            let res = collect_timing(
+                &parse(&[
+                    "Parse: (1.0ms)",
+                    r#"::aoc-result::{"part":0,"answer":null,"nanos":1000000,"samples":1,"status":"solved"}"#,
+                    r#"::aoc-result::{"part":1,"answer":"1","nanos":2000,"samples":1,"status":"solved"}"#,
+                    r#"::aoc-result::{"part":2,"answer":"2","nanos":3000,"samples":1,"status":"solved"}"#,
+                ]),
+                day!(5),
+            );
+            assert_approx_eq!(res.total_nanos, 1005000_f64);
+            assert_eq!(res.parse.unwrap().to_string(), "1.0ms");
+            assert_eq!(res.part_1.unwrap().to_string(), "2.0µs");
+        }
+
+        #[test]
+        fn parses_panicked_parts() {
+            let results = parse(&[
+                "Part 1: ✖ panicked: boom at src/bin/01.rs:3:5",
+                r#"::aoc-result::{"part":1,"answer":null,"nanos":10,"samples":1,"status":"panicked"}"#,
+                r#"::aoc-result::{"part":2,"answer":"10","nanos":20,"samples":1,"status":"solved"}"#,
+            ]);
+            assert_eq!(results[0].status, PartStatus::Panicked);
+
+            let res = collect_timing(&results, day!(1));
+            assert!(res.part_1.is_none());
+            assert_eq!(res.part_2.unwrap().to_string(), "20.0ns");
+        }
+
+        #[test]
+        fn roundtrips_result_records() {
+            let result = PartResult {
+                part: 2,
+                answer: Some("multi\nline".into()),
+                nanos: 1234.0,
+                samples: 10,
+                status: PartStatus::Solved,
+                stats: None,
+                memory: Some(MemoryStats {
+                    peak_bytes: 2048,
+                    total_bytes: 4096,
+                    allocations: 3,
+                }),
+                check: None,
+            };
+            let parsed = PartResult::from_line(&result.to_line()).unwrap().unwrap();
+            assert_eq!(parsed, result);
+        }
+
+        #[test]
+        fn fails_runs_that_exit_without_results() {
+            let failure = Command::new("cargo")
+                .arg("--no-such-flag")
+                .output()
+                .unwrap();
+            let success = Command::new("cargo").arg("--version").output().unwrap();
+
+            let run = |status, results| SolutionRun {
+                results,
+                status: Some(status),
+                ..SolutionRun::default()
+            };
+
+            assert!(run(failure.status, vec![]).is_failed());
+            assert!(!run(success.status, vec![]).is_failed());
+            // failing parts exit with an error, too, but are counted from their records.
+            assert!(!run(failure.status, parse(&[r#"::aoc-result::{"part":1,"answer":null,"nanos":10,"samples":1,"status":"failed"}"#])).is_failed());
+            assert!(!SolutionRun::default().is_failed());
         }
     }
 }
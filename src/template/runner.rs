@@ -1,56 +1,352 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
-use std::time::{Duration, Instant};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::{cmp, env, process};
 
+use tinyjson::JsonValue;
+
+use crate::template::alloc::{self, MemoryStats};
+use crate::template::answers::{Answers, CheckStatus};
+use crate::template::commands::CommandError;
+use crate::template::input::InputSource;
+use crate::template::stats::BenchStats;
+use crate::template::submissions::{Attempt, SubmissionOutcome, Submissions};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, data_dir, Puzzle, ANSI_ITALIC, ANSI_RESET};
+
+/// Prefix of the lines that carry a [`PartResult`] record on stdout when a solution is run with `--json`.
+pub const RESULT_PREFIX: &str = "::aoc-result::";
+
+/// Part number of the record of a shared parse step, see [`run_parse_with_options`].
+pub const PARSE_PART: u8 = 0;
+
+/// Options that control how a solution part is run.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the part instead of running it once.
+    pub is_timed: bool,
+    /// Emit a [`PartResult`] record after the human-readable output.
+    pub emit_json: bool,
+    /// Check the answer against `data/answers.json`.
+    pub check: bool,
+}
+
+impl RunOptions {
+    /// Read the options from the command-line flags `--time`, `--json` and `--check`.
+    /// Answers are only checked when running on the puzzle input.
+    pub fn from_args() -> Self {
+        Self {
+            is_timed: env::args().any(|x| x == "--time"),
+            emit_json: env::args().any(|x| x == "--json"),
+            check: env::args().any(|x| x == "--check")
+                && InputSource::from_args().is_puzzle_input(),
+        }
+    }
+}
+
+/// Return value of a solution part: an `Option` of the answer, where `None` means "not implemented",
+/// or a `Result` of an answer or an optional answer for parts that can fail.
+pub trait PartOutput {
+    type Answer: Display;
+
+    fn into_result(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    type Answer = T;
+
+    fn into_result(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T: Answer, E: Display> PartOutput for Result<T, E> {
+    type Answer = T::Value;
+
+    fn into_result(self) -> Result<Option<T::Value>, String> {
+        self.map(Answer::into_option).map_err(|e| e.to_string())
+    }
+}
+
+/// Types that can be returned in the `Ok` variant of a fallible part.
+/// Implement this for your own answer types to return them in a `Result`.
+pub trait Answer {
+    type Value: Display;
+
+    fn into_option(self) -> Option<Self::Value>;
+}
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+impl<T: Display> Answer for Option<T> {
+    type Value = T;
+
+    fn into_option(self) -> Option<T> {
+        self
+    }
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                type Value = $t;
+
+                fn into_option(self) -> Option<$t> {
+                    Some(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char, bool, String
+);
+
+impl<'a> Answer for &'a str {
+    type Value = &'a str;
+
+    fn into_option(self) -> Option<&'a str> {
+        Some(self)
+    }
+}
+
+pub fn run_part<I: Copy, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+) -> PartResult {
+    let result = run_part_with_options(func, input, puzzle, part, &RunOptions::from_args());
+    if let Some(Err(e)) = submit_result(result.answer.as_deref(), puzzle, part) {
+        CommandError::from(e).exit();
+    }
+    result
+}
+
+/// Run and print a solution part, returning its result record.
+pub fn run_part_with_options<I: Copy, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+    options: &RunOptions,
+) -> PartResult {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (outcome, duration, stats, memory) = run_timed(
+        |input| func(input).into_result(),
+        input,
+        options.is_timed,
+        |result| {
+            if let Ok(result) = result {
+                print_result(result, &part_str, "", "");
+            }
+        },
+    );
+
+    // a failed part is reported with its status and a message in place of the answer.
+    let (result, failure) = match outcome {
+        Ok(Ok(result)) => (result, None),
+        Ok(Err(error)) => (None, Some((PartStatus::Failed, format!("error: {error}")))),
+        Err(panic) => (
+            None,
+            Some((PartStatus::Panicked, format!("panicked: {panic}"))),
+        ),
+    };
+
+    let mut record = PartResult::new(part, result.as_ref(), &duration, stats);
+    record.memory = memory;
+
+    if let Some((status, _)) = failure {
+        record.status = status;
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if options.check {
+        let answers =
+            Answers::read_from_file(puzzle.year).unwrap_or_else(|e| CommandError::Failed(e).exit());
+        record.check = Some(answers.check(puzzle.day, part, record.answer.as_deref()));
+    }
+
+    match &failure {
+        Some((_, message)) => println!(
+            "{part_str}: ✖ {message}{}",
+            record.check.map_or("", format_check)
+        ),
+        None => print_result(
+            &result,
+            &part_str,
+            &format_duration(&duration, stats.as_ref(), memory.as_ref()),
+            record.check.map_or("", format_check),
+        ),
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if options.emit_json {
+        emit_result(&record);
     }
+
+    record
+}
+
+/// Run and print the parse step that is shared by both parts of a solution.
+/// Returns the parsed input, or `None` if parsing panicked, together with the result record of the step.
+pub fn run_parse_with_options<'a, P>(
+    func: impl Fn(&'a str) -> P,
+    input: &'a str,
+    options: &RunOptions,
+) -> (Option<P>, PartResult) {
+    let (outcome, duration, stats, memory) =
+        run_timed(func, input, options.is_timed, |_| print!("Parse:"));
+
+    let mut record = PartResult::new::<&str>(PARSE_PART, None, &duration, stats);
+    record.memory = memory;
+
+    let parsed = match outcome {
+        Ok(parsed) => {
+            record.status = PartStatus::Solved;
+            print!("\r");
+            println!(
+                "Parse:{}",
+                format_duration(&duration, stats.as_ref(), memory.as_ref())
+            );
+            Some(parsed)
+        }
+        Err(panic) => {
+            record.status = PartStatus::Panicked;
+            println!("Parse: ✖ panicked: {panic}");
+            None
+        }
+    };
+
+    if options.emit_json {
+        emit_result(&record);
+    }
+
+    (parsed, record)
+}
+
+/// Exit with a non-zero status if any of `results` failed or did not match its stored answer.
+/// Panics keep the exit status of an uncaught panic.
+pub fn exit_on_failure(results: &[PartResult]) {
+    if results.iter().any(|r| r.status == PartStatus::Panicked) {
+        process::exit(101);
+    }
+
+    if results
+        .iter()
+        .any(|r| r.status == PartStatus::Failed || r.check == Some(CheckStatus::Incorrect))
+    {
+        process::exit(CommandError::SolutionFailed.exit_code());
+    }
+}
+
+/// A solution that is compiled into the current binary, see [`crate::template::registry`].
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: Puzzle,
+    pub run: fn(&str, &RunOptions) -> Vec<PartResult>,
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When benched, the returned duration is the median of all samples.
+/// With the `count-allocs` feature, the memory usage of the first run is returned as well.
+/// If the function panics, the panic message is returned instead of a result and it is not benched.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (
+    Result<T, String>,
+    Duration,
+    Option<BenchStats>,
+    Option<MemoryStats>,
+) {
+    alloc::reset();
     let timer = Instant::now();
-    let result = {
+    let result = catch_panic(|| {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
         func(input)
-    };
+    });
     let base_time = timer.elapsed();
+    let memory = alloc::snapshot();
+
+    let result = match result {
+        Ok(result) => result,
+        Err(message) => return (Err(message), base_time, None, memory),
+    };
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let stats = if is_timed {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        None
     };
 
-    (result, run.0, run.1)
+    let duration = stats.as_ref().map_or(base_time, BenchStats::representative);
+
+    (Ok(result), duration, stats, memory)
+}
+
+thread_local! {
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Run `func` and catch a panic in it, returning its message and location.
+/// The default panic output is suppressed for caught panics.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static INSTALL_HOOK: Once = Once::new();
+
+    // the hook is process-wide, so it is installed once and only intercepts panics of catching threads.
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if !IS_CATCHING.with(Cell::get) {
+                default_hook(info);
+                return;
+            }
+
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Box<dyn Any>".into());
+
+            let message = match info.location() {
+                Some(location) => format!("{message} at {location}"),
+                None => message,
+            };
+
+            PANIC_MESSAGE.with(|m| *m.borrow_mut() = Some(message));
+        }));
+    });
+
+    IS_CATCHING.with(|c| c.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING.with(|c| c.set(false));
+
+    result.map_err(|_| {
+        PANIC_MESSAGE
+            .with(|m| m.borrow_mut().take())
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
+
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Option<BenchStats> {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -59,44 +355,254 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
-    let mut timers: Vec<Duration> = vec![];
+    // warm up caches and branch predictors before taking measurements.
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input)));
+    }
+
+    let mut timers: Vec<u128> = Vec::with_capacity(usize::try_from(bench_iterations).ok()?);
 
     for _ in 0..bench_iterations {
         let timer = Instant::now();
         black_box(func(black_box(input)));
-        timers.push(timer.elapsed());
+        timers.push(timer.elapsed().as_nanos());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Outcome of running a single solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+    Panicked,
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+impl PartStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Failed => "failed",
+            PartStatus::Panicked => "panicked",
+        }
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Machine-readable record of a single solution part.
+/// Emitted by solution binaries under `--json` and consumed by `run_multi`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub nanos: f64,
+    pub samples: u128,
+    pub status: PartStatus,
+    pub stats: Option<BenchStats>,
+    pub memory: Option<MemoryStats>,
+    pub check: Option<CheckStatus>,
+}
+
+impl PartResult {
+    fn new<T: Display>(
+        part: u8,
+        answer: Option<&T>,
+        duration: &Duration,
+        stats: Option<BenchStats>,
+    ) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = duration.as_nanos() as f64;
+
+        Self {
+            part,
+            answer: answer.map(ToString::to_string),
+            nanos,
+            samples: stats.map_or(1, |s| s.samples),
+            status: if answer.is_some() {
+                PartStatus::Solved
+            } else {
+                PartStatus::Unsolved
+            },
+            stats,
+            memory: None,
+            check: None,
+        }
+    }
+
+    /// Parses a line of solution output. Returns `None` if the line is not a result record.
+    pub fn from_line(line: &str) -> Option<Result<Self, String>> {
+        let json = line.strip_prefix(RESULT_PREFIX)?;
+        Some(
+            json.parse::<JsonValue>()
+                .map_err(|_| "result record is not valid JSON.".to_string())
+                .and_then(|value| PartResult::try_from(&value)),
+        )
+    }
+
+    /// Serializes the record to a single output line.
+    pub fn to_line(&self) -> String {
+        let json = JsonValue::from(self)
+            .stringify()
+            .expect("result record should be serializable");
+        format!("{RESULT_PREFIX}{json}")
+    }
+}
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "memory".into(),
+            match &value.memory {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "check".into(),
+            match value.check {
+                Some(CheckStatus::Correct) => JsonValue::String("correct".into()),
+                Some(CheckStatus::Incorrect) => JsonValue::String("incorrect".into()),
+                Some(CheckStatus::Unknown) => JsonValue::String("unknown".into()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected result.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected result.nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected result.samples to be a number.")?;
+
+        let status = match json.get("status").and_then(|v| v.get::<String>()) {
+            Some(s) if s == "solved" => PartStatus::Solved,
+            Some(s) if s == "unsolved" => PartStatus::Unsolved,
+            Some(s) if s == "failed" => PartStatus::Failed,
+            Some(s) if s == "panicked" => PartStatus::Panicked,
+            _ => return Err("Expected result.status to be a known status.".into()),
+        };
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+            _ => None,
+        };
+
+        let memory = match json.get("memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        let check = match json.get("check").and_then(|v| v.get::<String>()) {
+            Some(s) if s == "correct" => Some(CheckStatus::Correct),
+            Some(s) if s == "incorrect" => Some(CheckStatus::Incorrect),
+            Some(s) if s == "unknown" => Some(CheckStatus::Unknown),
+            _ => None,
+        };
+
+        Ok(PartResult {
+            part,
+            answer: answer.cloned(),
+            nanos,
+            samples,
+            status,
+            stats,
+            memory,
+            check,
+        })
+    }
+}
+
+fn emit_result(result: &PartResult) {
+    println!("{}", result.to_line());
+}
+
+fn format_duration(
+    duration: &Duration,
+    stats: Option<&BenchStats>,
+    memory: Option<&MemoryStats>,
+) -> String {
+    let memory = memory.map_or(String::new(), |m| format!("; {m}"));
+
+    match stats {
+        None => format!(" ({duration:.1?}{memory})"),
+        Some(stats) => format!(
+            " ({duration:.1?} @ {} samples; {}{memory})",
+            stats.samples,
+            stats.summary()
+        ),
+    }
+}
+
+fn format_check(check: CheckStatus) -> &'static str {
+    match check {
+        CheckStatus::Correct => " ✔",
+        CheckStatus::Incorrect => " ✘",
+        CheckStatus::Unknown => " ? (no stored answer)",
+    }
+}
+
+fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str, check_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+                let str = format!("{part}: ▼ {duration_str}{check_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -105,7 +611,8 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str =
+                    format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}{check_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -119,47 +626,190 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 print!("{part}: ✖");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: ✖{check_str}             ");
             }
         }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
+///  1. we are in `--release` mode and run on the puzzle input.
+///  2. the part produced an answer.
+///  3. aoc-cli is installed, or the built-in client is configured.
+///  4. the answer does not conflict with a previous attempt in `data/submissions.json`.
+fn submit_result(
+    result: Option<&str>,
+    puzzle: Puzzle,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<String, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
-
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let Some(part_submit) = args.get(part_index).and_then(|x| x.parse::<u8>().ok()) else {
+        CommandError::Usage(
+            "unexpected command-line input. Format: cargo solve 1 --submit 1".into(),
+        )
+        .exit();
     };
 
     if part_submit != part {
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let Some(result) = result else {
+        eprintln!("Not submitting part {part} as it did not produce an answer.");
+        return None;
+    };
+
+    let source = InputSource::from_args();
+    if !source.is_puzzle_input() {
+        eprintln!("Not submitting an answer computed from {source}.");
+        return None;
+    }
+
+    // `cargo solve` checks this before it starts the solution, this covers running the bin directly.
+    if let Err(e) = aoc_cli::check() {
+        return Some(Err(e));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    let day = puzzle.day;
+    // without the recorded attempts, the guard cannot tell known-wrong answers apart.
+    let mut submissions = match Submissions::read_from_file(puzzle.year) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Refusing to submit: {e}");
+            return None;
+        }
+    };
+
+    if let Err(reason) = submissions.guard(day, part, &answer) {
+        eprintln!("Refusing to submit: {reason}");
+        eprintln!(
+            "Edit or remove the entry in {} to submit anyway.",
+            data_dir(puzzle.year).join("submissions.json").display()
+        );
+        return None;
+    }
+
+    println!("Submitting result...");
+    let output = aoc_cli::submit(puzzle, part, &answer);
+
+    let stdout = match &output {
+        Ok(response) => Some(response.clone()),
+        Err(aoc_cli::AocCommandError::BadExitStatus(output)) => {
+            Some(String::from_utf8_lossy(&output.stdout).to_string())
+        }
+        Err(_) => None,
+    };
+
+    if let Some(stdout) = stdout {
+        let outcome = SubmissionOutcome::classify(&stdout);
+
+        submissions.record(Attempt {
+            day,
+            part,
+            answer: answer.trim().to_string(),
+            outcome,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        });
+
+        match submissions.store_file(puzzle.year) {
+            Ok(()) => println!("Recorded submission as {outcome}."),
+            Err(e) => eprintln!("Failed to record submission: {e}"),
+        }
+
+        if outcome == SubmissionOutcome::Correct {
+            store_answer(puzzle, part, &answer);
+        }
+    }
+
+    Some(output)
+}
+
+/// Record an accepted answer in the answers file of its year.
+fn store_answer(puzzle: Puzzle, part: u8, answer: &str) {
+    let mut answers = match Answers::read_from_file(puzzle.year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Not storing accepted answer {answer}, fix the answers file first: {e}");
+            return;
+        }
+    };
+    answers.set(puzzle.day, part, answer);
+
+    match answers.store_file(puzzle.year) {
+        Ok(()) => println!(
+            "Stored accepted answer in {}.",
+            data_dir(puzzle.year).join("answers.json").display()
+        ),
+        Err(e) => eprintln!("Failed to store accepted answer: {e}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{catch_panic, run_part_with_options, PartOutput, PartStatus, RunOptions};
+    use crate::day;
+    use crate::template::Puzzle;
+
+    /// A part that returns a `Result`, e.g. when the input does not have a solution.
+    fn part_one(input: &str) -> Result<u32, String> {
+        input
+            .trim()
+            .parse()
+            .map_err(|_| format!("expected a number, got \"{input}\""))
+    }
+
+    #[test]
+    fn runs_result_parts() {
+        let puzzle = Puzzle::new(None, day!(1));
+        let options = RunOptions::default();
+
+        let solved = run_part_with_options(part_one, "42", puzzle, 1, &options);
+        assert_eq!(solved.status, PartStatus::Solved);
+        assert_eq!(solved.answer.as_deref(), Some("42"));
+
+        let failed = run_part_with_options(part_one, "abc", puzzle, 1, &options);
+        assert_eq!(failed.status, PartStatus::Failed);
+        assert_eq!(failed.answer, None);
+    }
+
+    #[test]
+    fn converts_part_outputs() {
+        assert_eq!(Some(1).into_result(), Ok(Some(1)));
+        assert_eq!(None::<u32>.into_result(), Ok(None));
+        assert_eq!(Ok::<_, String>(1).into_result(), Ok(Some(1)));
+        assert_eq!(Ok::<_, String>("abc").into_result(), Ok(Some("abc")));
+        assert_eq!(Ok::<_, &str>(None::<u32>).into_result(), Ok(None));
+        assert_eq!(
+            Err::<u32, _>("no solution").into_result(),
+            Err("no solution".to_string())
+        );
+        assert_eq!(
+            Err::<Option<u32>, _>("no solution").into_result(),
+            Err("no solution".to_string())
+        );
+    }
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+
+        let message = catch_panic(|| -> u8 { panic!("boom") }).unwrap_err();
+        assert!(message.starts_with("boom at src/template/runner.rs:"));
+
+        // formatted messages are passed as a `String` payload.
+        let message = catch_panic(|| -> u8 { panic!("boom {}", 2) }).unwrap_err();
+        assert!(message.starts_with("boom 2 at src/template/runner.rs:"));
+    }
 }
@@ -0,0 +1,172 @@
+/// Summary statistics over the samples collected while benching a solution part.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+/// Statistics over a set of benchmark samples. All durations are stored as nanoseconds.
+///
+/// Samples outside of the [Tukey fences](https://en.wikipedia.org/wiki/Outlier#Tukey's_fences)
+/// are counted as outliers and excluded from the mean and standard deviation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub mean: f64,
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub std_dev: f64,
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Computes statistics for a set of samples given in nanoseconds.
+    /// Returns `None` if no samples were taken.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[u128]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 25.0);
+        let q3 = percentile(&sorted, 75.0);
+        let iqr = q3 - q1;
+        let (lower_fence, upper_fence) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let inliers: Vec<f64> = sorted
+            .iter()
+            .map(|x| *x as f64)
+            .filter(|x| *x >= lower_fence && *x <= upper_fence)
+            .collect();
+
+        let mean = inliers.iter().sum::<f64>() / inliers.len() as f64;
+        let variance =
+            inliers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / inliers.len() as f64;
+
+        Some(Self {
+            samples: sorted.len() as u128,
+            mean,
+            min: sorted[0] as f64,
+            median: percentile(&sorted, 50.0),
+            p95: percentile(&sorted, 95.0),
+            std_dev: variance.sqrt(),
+            outliers: (sorted.len() - inliers.len()) as u128,
+        })
+    }
+
+    /// The figure that is reported as the benchmark result of a part.
+    pub fn representative(&self) -> Duration {
+        to_duration(self.median)
+    }
+
+    /// Formats the statistics for display next to a result, e.g. `min 1.0ms, p95 1.2ms, σ 40.0µs, 3 outliers`.
+    pub fn summary(&self) -> String {
+        format!(
+            "min {:.1?}, p95 {:.1?}, σ {:.1?}, {} outliers",
+            to_duration(self.min),
+            to_duration(self.p95),
+            to_duration(self.std_dev),
+            self.outliers
+        )
+    }
+}
+
+/// Linearly interpolated percentile of a sorted, non-empty slice.
+#[allow(clippy::cast_precision_loss)]
+fn percentile(sorted: &[u128], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - rank.floor();
+    sorted[lower] as f64 * (1.0 - weight) + sorted[upper] as f64 * weight
+}
+
+/// Converts nanoseconds, e.g. a median, to a [`Duration`] for display.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            mean: number("mean")?,
+            min: number("min")?,
+            median: number("median")?,
+            p95: number("p95")?,
+            std_dev: number("std_dev")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchStats;
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn computes_order_statistics() {
+        let samples: Vec<u128> = (1..=100).rev().collect();
+        let stats = BenchStats::from_samples(&samples).unwrap();
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 50.5);
+        assert_eq!(stats.p95, 95.05);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.mean, 50.5);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let mut samples: Vec<u128> = (90..=110).collect();
+        samples.push(10_000);
+        let stats = BenchStats::from_samples(&samples).unwrap();
+        assert_eq!(stats.samples, 22);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.mean, 100.0);
+        assert!(stats.std_dev < 10.0);
+    }
+}
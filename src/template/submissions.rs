@@ -0,0 +1,401 @@
+/// Module that records every submitted answer, so known-wrong answers are not submitted twice.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, Day};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// The response of the Advent of Code server to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    AlreadySolved,
+    Unknown,
+}
+
+impl SubmissionOutcome {
+    /// Classifies the output that aoc-cli prints after submitting an answer.
+    pub fn classify(output: &str) -> Self {
+        let output = output.to_lowercase();
+
+        if output.contains("that's the right answer") {
+            Self::Correct
+        } else if output.contains("your answer is too high") {
+            Self::TooHigh
+        } else if output.contains("your answer is too low") {
+            Self::TooLow
+        } else if output.contains("that's not the right answer") {
+            Self::Wrong
+        } else if output.contains("you gave an answer too recently") {
+            Self::RateLimited
+        } else if output.contains("did you already complete it") {
+            Self::AlreadySolved
+        } else {
+            Self::Unknown
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::Wrong => "wrong",
+            Self::TooHigh => "too_high",
+            Self::TooLow => "too_low",
+            Self::RateLimited => "rate_limited",
+            Self::AlreadySolved => "already_solved",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str().replace('_', " "))
+    }
+}
+
+impl FromStr for SubmissionOutcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Self::Correct,
+            Self::Wrong,
+            Self::TooHigh,
+            Self::TooLow,
+            Self::RateLimited,
+            Self::AlreadySolved,
+            Self::Unknown,
+        ]
+        .into_iter()
+        .find(|outcome| outcome.as_str() == s)
+        .ok_or_else(|| format!("unknown submission outcome `{s}`."))
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attempt {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmissionOutcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Every answer that was submitted, in the order of submission.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Submissions {
+    pub data: Vec<Attempt>,
+}
+
+impl Submissions {
+    /// Read submissions from their file. If not present, returns an empty record.
+    /// A file that cannot be read or parsed is an error, so it is never overwritten with fewer attempts.
+    pub fn read_from_file(year: Option<u16>) -> Result<Self, String> {
+        Submissions::read_from_path(&data_dir(year).join(SUBMISSIONS_FILE_NAME))
+    }
+
+    fn read_from_path(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Submissions::try_from(contents)
+                .map_err(|e| format!("could not parse \"{}\": {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(format!("could not read \"{}\": {e}", path.display())),
+        }
+    }
+
+    pub fn store_file(&self, year: Option<u16>) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(data_dir(year).join(SUBMISSIONS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.data.push(attempt);
+    }
+
+    /// Returns the reason why `answer` should not be submitted for a part, if any.
+    pub fn guard(&self, day: Day, part: u8, answer: &str) -> Result<(), String> {
+        let answer = answer.trim();
+        let numeric = answer.parse::<i128>().ok();
+
+        for attempt in self.data.iter().filter(|a| a.day == day && a.part == part) {
+            let previous = attempt.answer.as_str();
+
+            match attempt.outcome {
+                SubmissionOutcome::Correct => {
+                    return Err(format!("part {part} was already solved with `{previous}`."));
+                }
+                SubmissionOutcome::Wrong
+                | SubmissionOutcome::TooHigh
+                | SubmissionOutcome::TooLow
+                    if previous == answer =>
+                {
+                    return Err(format!(
+                        "`{answer}` was already rejected as {}.",
+                        attempt.outcome
+                    ));
+                }
+                SubmissionOutcome::TooHigh => {
+                    if let (Some(n), Ok(bound)) = (numeric, previous.parse::<i128>()) {
+                        if n >= bound {
+                            return Err(format!("`{previous}` was already too high."));
+                        }
+                    }
+                }
+                SubmissionOutcome::TooLow => {
+                    if let (Some(n), Ok(bound)) = (numeric, previous.parse::<i128>()) {
+                        if n <= bound {
+                            return Err(format!("`{previous}` was already too low."));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Attempt::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Attempt> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Attempt) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.as_str().into()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Attempt {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected attempt to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected attempt.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected attempt.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected attempt.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected attempt.outcome to be a string.")?
+            .parse()?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map_or(0, |v| *v as u64);
+
+        Ok(Attempt {
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Attempt, SubmissionOutcome, Submissions};
+    use crate::day;
+    use std::{env, fs, process};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn reports_unparsable_files() {
+        let dir = env::temp_dir().join(format!("aoc-submissions-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("submissions.json");
+        fs::write(&path, "{ \"data\": [").unwrap();
+        let result = Submissions::read_from_path(&path);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.unwrap_err().starts_with("could not parse"));
+        assert_eq!(
+            Submissions::read_from_path(&path),
+            Ok(Submissions::default())
+        );
+    }
+
+    fn attempt(part: u8, answer: &str, outcome: SubmissionOutcome) -> Attempt {
+        Attempt {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            outcome,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn classifies_aoc_cli_output() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                SubmissionOutcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                SubmissionOutcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmissionOutcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, ...",
+                SubmissionOutcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait.",
+                SubmissionOutcome::RateLimited,
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                SubmissionOutcome::AlreadySolved,
+            ),
+            ("", SubmissionOutcome::Unknown),
+        ];
+
+        for (output, outcome) in cases {
+            assert_eq!(SubmissionOutcome::classify(output), outcome);
+        }
+    }
+
+    #[test]
+    fn guards_known_wrong_answers() {
+        let submissions = Submissions {
+            data: vec![attempt(1, "42", SubmissionOutcome::Wrong)],
+        };
+        assert!(submissions.guard(day!(1), 1, "42").is_err());
+        assert!(submissions.guard(day!(1), 1, "43").is_ok());
+        assert!(submissions.guard(day!(1), 2, "42").is_ok());
+        assert!(submissions.guard(day!(2), 1, "42").is_ok());
+    }
+
+    #[test]
+    fn guards_bounds() {
+        let submissions = Submissions {
+            data: vec![
+                attempt(1, "100", SubmissionOutcome::TooHigh),
+                attempt(1, "10", SubmissionOutcome::TooLow),
+            ],
+        };
+        assert!(submissions.guard(day!(1), 1, "100").is_err());
+        assert!(submissions.guard(day!(1), 1, "150").is_err());
+        assert!(submissions.guard(day!(1), 1, "10").is_err());
+        assert!(submissions.guard(day!(1), 1, "-5").is_err());
+        assert!(submissions.guard(day!(1), 1, "50").is_ok());
+        assert!(submissions.guard(day!(1), 1, "abc").is_ok());
+    }
+
+    #[test]
+    fn guards_solved_parts() {
+        let submissions = Submissions {
+            data: vec![
+                attempt(1, "7", SubmissionOutcome::RateLimited),
+                attempt(1, "8", SubmissionOutcome::Correct),
+            ],
+        };
+        assert!(submissions.guard(day!(1), 1, "7").is_err());
+        assert!(submissions.guard(day!(1), 2, "7").is_ok());
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = Submissions {
+            data: vec![
+                attempt(1, "100", SubmissionOutcome::TooHigh),
+                attempt(2, "abc", SubmissionOutcome::AlreadySolved),
+            ],
+        };
+        let json = JsonValue::from(&submissions).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap(), submissions);
+    }
+}
@@ -1,21 +1,99 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    str::FromStr,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::alloc::MemoryStats;
+use crate::template::stats::{to_duration, BenchStats};
+use crate::template::{data_dir, Day};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
+/// `parse` is only set for solutions with a shared parse step.
+/// Parts are formatted for display only when they are rendered, see [`PartTiming`].
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
+/// Whether a part finished while it was benchmarked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimingStatus {
+    Solved,
+    /// The day was killed by the timeout of `cargo all` or `cargo time` before the part finished.
+    TimedOut,
+}
+
+impl TimingStatus {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            TimingStatus::Solved => "solved",
+            TimingStatus::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// Benchmark result of a single part, or of the shared parse step.
+/// `stats` hold the full distribution of the samples, `memory` is only set with the `count-allocs` feature.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartTiming {
+    pub status: TimingStatus,
+    /// Median of all samples in nanoseconds. Zero for parts that timed out.
+    pub nanos: f64,
+    pub samples: u128,
+    pub stats: Option<BenchStats>,
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartTiming {
+    /// A part that was run `samples` times with a median of `nanos`.
+    pub fn solved(nanos: f64, samples: u128) -> Self {
+        Self {
+            status: TimingStatus::Solved,
+            nanos,
+            samples,
+            stats: None,
+            memory: None,
+        }
+    }
+
+    /// A part that did not finish before its day was killed.
+    pub fn timed_out() -> Self {
+        Self {
+            status: TimingStatus::TimedOut,
+            nanos: 0.0,
+            samples: 0,
+            stats: None,
+            memory: None,
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.status == TimingStatus::Solved
+    }
+}
+
+/// Formats the median like the runner does, e.g. `74.1ms`, or `timed out`.
+impl std::fmt::Display for PartTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.status {
+            TimingStatus::Solved => write!(f, "{:.1?}", to_duration(self.nanos)),
+            TimingStatus::TimedOut => f.write_str("timed out"),
+        }
+    }
+}
+
 /// Represents benchmark times for a set of days.
-/// Can be serialized from / to JSON.
+/// Can be serialized from / to JSON. Each year keeps its own file in its [`data_dir`].
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
@@ -23,18 +101,26 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Option<u16>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(data_dir(year).join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Files written before parts were stored as numbers are migrated when they are read.
+    /// A file that cannot be read, parsed or migrated is an error, so it is never overwritten with fewer timings.
+    pub fn read_from_file(year: Option<u16>) -> Result<Self, String> {
+        Timings::read_from_path(&data_dir(year).join(TIMINGS_FILE_NAME))
+    }
+
+    fn read_from_path(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Timings::try_from(contents)
+                .map_err(|e| format!("could not parse \"{}\": {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(format!("could not read \"{}\": {e}", path.display())),
+        }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,7 +137,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -61,12 +147,72 @@ impl Timings {
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
+        self.data.iter().any(|t| {
+            t.day == day
+                && t.part_1.is_some_and(|p| p.is_solved())
+                && t.part_2.is_some_and(|p| p.is_solved())
+        })
+    }
+}
+
+impl Timing {
+    /// A day without any timed parts.
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0.0,
+        }
+    }
+
+    /// Timing of a part, if it was run. Part `0` is the shared parse step.
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            0 => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Benchmark time of a part in nanoseconds, if it was timed. Part `0` is the shared parse step.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        self.part(part).filter(|p| p.is_solved()).map(|p| p.nanos)
+    }
+
+    /// Whether the day was killed by a timeout before all of its parts finished.
+    pub fn is_timed_out(&self) -> bool {
+        [&self.parse, &self.part_1, &self.part_2]
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|p| p.is_some_and(|p| p.status == TimingStatus::TimedOut))
+    }
+
+    /// Memory usage of both parts combined, if it was recorded.
+    pub fn memory(&self) -> Option<MemoryStats> {
+        let part_1 = self.part_1.and_then(|p| p.memory);
+        let part_2 = self.part_2.and_then(|p| p.memory);
+
+        match (part_1, part_2) {
+            (Some(a), Some(b)) => Some(a.combine(&b)),
+            (a, b) => a.or(b),
+        }
     }
 }
 
+/// Parses a duration formatted with `{:?}`, e.g. `74.13ms`, to nanoseconds.
+/// Only needed to migrate timings that were stored as formatted strings.
+fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse = |postfix: &str| s.strip_suffix(postfix)?.parse::<f64>().ok();
+
+    parse("ns")
+        .or_else(|| parse("µs").map(|x| x * 1000_f64))
+        .or_else(|| parse("ms").map(|x| x * 1_000_000_f64))
+        .or_else(|| parse("s").map(|x| x * 1_000_000_000_f64))
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -107,6 +253,77 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&PartTiming> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let status = match json.get("status").and_then(|v| v.get::<String>()) {
+            Some(s) if s == "solved" => TimingStatus::Solved,
+            Some(s) if s == "timed_out" => TimingStatus::TimedOut,
+            _ => return Err("Expected part timing.status to be solved or timed_out.".into()),
+        };
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected part timing.{key} to be a number."))
+        };
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+            _ => None,
+        };
+
+        let memory = match json.get("memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(PartTiming {
+            status,
+            nanos: number("nanos")?,
+            samples: number("samples")? as u128,
+            stats,
+            memory,
+        })
+    }
+}
+
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
@@ -114,24 +331,16 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::from(parse));
+        }
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                part.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
 
         JsonValue::Object(map)
     }
@@ -151,76 +360,170 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
-        Ok(Timing {
+        let parse_part = |key: &str| match json.get(key) {
+            None => Err(format!("Expected timing.{key} to be present.")),
+            Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(formatted)) => migrate_part(json, key, formatted).map(Some),
+            Some(v) => PartTiming::try_from(v).map(Some),
+        };
+
+        // the parse step is optional, as most solutions parse their input within each part.
+        let parse = match json.get("parse") {
+            Some(_) => parse_part("parse")?,
+            None => None,
+        };
+
+        let mut timing = Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1: parse_part("part_1")?,
+            part_2: parse_part("part_2")?,
             total_nanos,
-        })
+        };
+
+        // timings stored as strings flagged the day as timed out, instead of its parts.
+        let is_legacy_timed_out = json
+            .get("timed_out")
+            .and_then(|v| v.get::<bool>().copied())
+            .unwrap_or(false);
+
+        if is_legacy_timed_out {
+            timing.part_1 = timing.part_1.or(Some(PartTiming::timed_out()));
+            timing.part_2 = timing.part_2.or(Some(PartTiming::timed_out()));
+        }
+
+        Ok(timing)
     }
 }
 
+/// Reads a part that was stored as a formatted median, e.g. `"part_1": "74.1ms"`,
+/// together with its optional `part_1_stats` and `part_1_memory`.
+fn migrate_part(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+    formatted: &str,
+) -> Result<PartTiming, String> {
+    let stats = match json.get(&format!("{key}_stats")) {
+        Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+        _ => None,
+    };
+
+    let memory = match json.get(&format!("{key}_memory")) {
+        Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+        _ => None,
+    };
+
+    // the stats hold the exact median, the formatted string is rounded.
+    let nanos = stats
+        .map(|s| s.median)
+        .or_else(|| parse_duration(formatted))
+        .ok_or(format!("Expected timing.{key} to be a duration."))?;
+
+    Ok(PartTiming {
+        status: TimingStatus::Solved,
+        nanos,
+        samples: stats.map_or(1, |s| s.samples),
+        stats,
+        memory,
+    })
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
+
+    fn timing(day: crate::template::Day, part_1: Option<f64>, part_2: Option<f64>) -> Timing {
+        let mut timing = Timing::new(day);
+        timing.part_1 = part_1.map(|nanos| PartTiming::solved(nanos, 1));
+        timing.part_2 = part_2.map(|nanos| PartTiming::solved(nanos, 1));
+        timing
+    }
 
     fn get_mock_timings() -> Timings {
-        Timings {
+        let mut timings = Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
+                timing(day!(1), Some(10e6), Some(20e6)),
+                timing(day!(2), Some(30e6), Some(40e6)),
+                timing(day!(4), Some(40e6), None),
             ],
-        }
+        };
+        timings.data[0].total_nanos = 3e+10;
+        timings.data[1].total_nanos = 7e+10;
+        timings.data[2].total_nanos = 4e+10;
+        timings
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use std::{env, fs, process};
+
+        use crate::{
+            day,
+            template::timings::{PartTiming, TimingStatus, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "status": "solved", "nanos": 1000000, "samples": 12, "stats": null, "memory": null }, "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(PartTiming::solved(1_000_000.0, 12)));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert!(!timing.is_timed_out());
+        }
+
+        #[test]
+        fn handles_timed_out_parts() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "status": "solved", "nanos": 1000, "samples": 1 }, "part_2": { "status": "timed_out", "nanos": 0, "samples": 0 }, "total_nanos": 1000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_2.unwrap().status, TimingStatus::TimedOut);
+            assert_eq!(timing.part_nanos(2), None);
+            assert!(timing.is_timed_out());
+            assert!(!timings.is_day_complete(day!(1)));
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats_and_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "status": "solved", "nanos": 1000000, "samples": 10, "stats": { "samples": 10, "mean": 1000000, "min": 900000, "median": 1000000, "p95": 1200000, "std_dev": 1000, "outliers": 1 }, "memory": { "peak_bytes": 1024, "total_bytes": 2048, "allocations": 4 } }, "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.unwrap();
+            let stats = part_1.stats.unwrap();
+            assert_eq!(stats.min, 900_000_f64);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(part_1.memory.unwrap().peak_bytes, 1024);
+            assert_eq!(timings.data[0].memory().unwrap().allocations, 4);
+        }
+
+        #[test]
+        fn migrates_formatted_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2ms", "part_1": "1.5µs", "part_2": "1ms", "total_nanos": 1000000, "part_2_stats": { "samples": 10, "mean": 1000000, "min": 900000, "median": 1000400, "p95": 1200000, "std_dev": 1000, "outliers": 1 }, "part_1_memory": { "peak_bytes": 1024, "total_bytes": 2048, "allocations": 4 } }, { "day": "02", "part_1": "1.0s", "part_2": null, "timed_out": true, "total_nanos": 1000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+
+            let first = &timings.data[0];
+            assert_eq!(first.part_nanos(0), Some(2_000_000.0));
+            assert_eq!(first.part_nanos(1), Some(1_500.0));
+            assert_eq!(first.part_1.unwrap().samples, 1);
+            assert_eq!(first.part_1.unwrap().memory.unwrap().peak_bytes, 1024);
+            // the stats hold the exact median.
+            assert_eq!(first.part_nanos(2), Some(1_000_400.0));
+            assert_eq!(first.part_2.unwrap().samples, 10);
+            assert!(!first.is_timed_out());
+
+            let second = &timings.data[1];
+            assert_eq!(second.part_nanos(1), Some(1_000_000_000.0));
+            assert_eq!(second.part_2, Some(PartTiming::timed_out()));
         }
 
         #[test]
@@ -230,6 +533,19 @@ mod tests {
             assert_eq!(timings.data.len(), 0);
         }
 
+        #[test]
+        fn reports_unmigratable_files() {
+            let dir = env::temp_dir().join(format!("aoc-timings-{}", process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("timings.json");
+            fs::write(&path, r#"{ "data": [{ "day": "01", "part_1": "fast", "part_2": null, "total_nanos": 0 }] }"#).unwrap();
+            let result = Timings::read_from_path(&path);
+            fs::remove_dir_all(&dir).unwrap();
+
+            assert!(result.unwrap_err().starts_with("could not parse"));
+            assert!(Timings::read_from_path(&path).unwrap().data.is_empty());
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_invalid_json() {
@@ -243,17 +559,25 @@ mod tests {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_status() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "status": "skipped", "nanos": 0, "samples": 0 }, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
     }
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{PartTiming, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
+            let value = JsonValue::from(timings);
             assert_eq!(
                 value
                     .get::<HashMap<String, JsonValue>>()
@@ -266,54 +590,97 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[0].parse = Some(PartTiming::solved(5e6, 3));
+            timings.data[2].part_2 = Some(PartTiming::timed_out());
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+
+            for (a, b) in timings.data.iter().zip(&parsed.data) {
+                assert_eq!(a.day, b.day);
+                assert_eq!(a.parse, b.parse);
+                assert_eq!(a.part_1, b.part_1);
+                assert_eq!(a.part_2, b.part_2);
+                assert_eq!(a.total_nanos, b.total_nanos);
+            }
+        }
+    }
+
+    mod formatting {
+        use crate::template::timings::PartTiming;
+
+        #[test]
+        fn formats_parts() {
+            assert_eq!(PartTiming::solved(74.0, 1).to_string(), "74.0ns");
+            assert_eq!(PartTiming::solved(74_130_000.0, 1).to_string(), "74.1ms");
+            assert_eq!(PartTiming::solved(2e9, 1).to_string(), "2.0s");
+            assert_eq!(PartTiming::timed_out().to_string(), "timed out");
+        }
     }
 
     mod is_day_complete {
-        use crate::{
-            day,
-            template::timings::{Timing, Timings},
-        };
+        use crate::{day, template::timings::Timings};
+
+        use super::timing;
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
-                }],
+                data: vec![timing(day!(1), Some(1e6), Some(2e6))],
             };
 
-            assert_eq!(timings.is_day_complete(day!(1)), true);
+            assert!(timings.is_day_complete(day!(1)));
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
-                }],
+                data: vec![timing(day!(1), Some(1e6), None)],
             };
 
-            assert_eq!(timings.is_day_complete(day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![timing(day!(1), None, None)],
             };
 
-            assert_eq!(timings.is_day_complete(day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
+        }
+    }
+
+    mod part_nanos {
+        use crate::{day, template::timings::PartTiming};
+
+        use super::timing;
+
+        #[test]
+        fn handles_parts() {
+            let mut timing = timing(day!(1), Some(74.5), Some(1_500.0));
+            timing.parse = Some(PartTiming::solved(2e6, 1));
+            assert_eq!(timing.part_nanos(0), Some(2_000_000.0));
+            assert_eq!(timing.part_nanos(1), Some(74.5));
+            assert_eq!(timing.part_nanos(2), Some(1_500.0));
+        }
+
+        #[test]
+        fn handles_missing_parts() {
+            assert_eq!(timing(day!(1), None, None).part_nanos(1), None);
+            assert_eq!(timing(day!(1), Some(1e6), None).part_nanos(2), None);
+            assert_eq!(timing(day!(1), Some(1e6), None).part_nanos(3), None);
+        }
+
+        #[test]
+        fn skips_timed_out_parts() {
+            let mut timing = timing(day!(1), Some(1e6), None);
+            timing.part_2 = Some(PartTiming::timed_out());
+            assert_eq!(timing.part_nanos(2), None);
         }
     }
 
@@ -329,12 +696,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +711,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 